@@ -0,0 +1,23 @@
+#!/bin/sh
+# unsafeなコードを通る例をMiriで実行し、未定義動作がないことを確認する
+# toy_vec_try_reserveはisize::MAXバイトの確保を要求するが、Miriはエラーを返さずに
+# 実行を打ち切るので対象にしない
+set -eu
+
+rustup component add --toolchain nightly miri rust-src
+
+for EXAMPLE in \
+    toy_vec_allocator \
+    toy_vec_array \
+    toy_vec_deque \
+    toy_vec_drain \
+    toy_vec_drop \
+    toy_vec_growth \
+    toy_vec_macro \
+    toy_vec_raw_parts \
+    toy_vec_retain \
+    toy_vec_small \
+    toy_vec_splice \
+    toy_vec_zst; do
+    cargo +nightly miri run --example "$EXAMPLE"
+done
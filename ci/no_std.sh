@@ -0,0 +1,13 @@
+#!/bin/sh
+# stdのないターゲット向けに、stdフィーチャを外してビルドできることを確認する
+# std::に依存するコードが紛れ込むと、このビルドが失敗する
+# allocフィーチャも外したビルドでは、alloc::に依存するコードが紛れ込んでいないことを確認する
+set -eu
+
+TARGET="${TARGET:-thumbv7em-none-eabihf}"
+
+rustup target add "$TARGET"
+for FEATURES in "" "alloc"; do
+    cargo build --no-default-features --features "$FEATURES" --target "$TARGET"
+    cargo clippy --no-default-features --features "$FEATURES" --target "$TARGET" -- -D warnings
+done
@@ -0,0 +1,64 @@
+// ドロップの回数や順序を調べる例で共有する型と補助関数
+// 例ごとに使わないものがあるので、未使用の警告は出さない
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::ops::Range;
+use toy_vec::ToyVec;
+
+// ドロップされた順に自分の番号を記録する型。panic_on_dropならドロップ時にパニックする
+// クローンは同じ番号を持つ
+#[derive(Clone)]
+pub struct Noisy<'a> {
+    pub id: u32,
+    pub log: &'a RefCell<Vec<u32>>,
+    pub panic_on_drop: bool,
+}
+
+impl<'a> Drop for Noisy<'a> {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.id);
+        if self.panic_on_drop {
+            panic!("Noisy {} panicked on drop", self.id);
+        }
+    }
+}
+
+pub fn noisy(id: u32, log: &RefCell<Vec<u32>>) -> Noisy<'_> {
+    Noisy {
+        id,
+        log,
+        panic_on_drop: false,
+    }
+}
+
+// ドロップ時にパニックするNoisy
+pub fn panicky(id: u32, log: &RefCell<Vec<u32>>) -> Noisy<'_> {
+    Noisy {
+        id,
+        log,
+        panic_on_drop: true,
+    }
+}
+
+pub fn noisy_vec(ids: Range<u32>, log: &RefCell<Vec<u32>>) -> ToyVec<Noisy<'_>> {
+    ids.map(|id| noisy(id, log)).collect()
+}
+
+pub fn ids(v: &[Noisy<'_>]) -> Vec<u32> {
+    v.iter().map(|n| n.id).collect()
+}
+
+// ドロップされた番号を並べ替えたもの。各要素がちょうど1回ずつドロップされたかを調べる
+pub fn dropped(log: &RefCell<Vec<u32>>) -> Vec<u32> {
+    let mut ids = log.borrow().clone();
+    ids.sort_unstable();
+    ids
+}
+
+// droppedと同じものを返し、記録を空にする
+pub fn take_dropped(log: &RefCell<Vec<u32>>) -> Vec<u32> {
+    let mut ids = log.take();
+    ids.sort_unstable();
+    ids
+}
@@ -0,0 +1,92 @@
+// ToyVecの伸長にかかる時間を、Box<[T]>を確保し直していた以前の実装と比べる
+// cargo run --release --example grow_bench で実行する
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use toy_vec::ToyVec;
+
+// 以前のToyVec。伸長のたびに新しい領域をデフォルト値で埋め、要素をひとつずつ移動していた
+struct OldToyVec<T> {
+    elements: Box<[T]>,
+    len: usize,
+}
+
+impl<T: Default> OldToyVec<T> {
+    fn new() -> Self {
+        Self {
+            elements: Self::allocate_in_heap(0),
+            len: 0,
+        }
+    }
+
+    fn allocate_in_heap(size: usize) -> Box<[T]> {
+        std::iter::repeat_with(Default::default)
+            .take(size)
+            .collect::<Vec<_>>()
+            .into_boxed_slice()
+    }
+
+    fn push(&mut self, element: T) {
+        if self.len == self.elements.len() {
+            self.grow();
+        }
+        self.elements[self.len] = element;
+        self.len += 1;
+    }
+
+    fn grow(&mut self) {
+        if self.elements.is_empty() {
+            self.elements = Self::allocate_in_heap(1);
+        } else {
+            let new_elements = Self::allocate_in_heap(self.elements.len() * 2);
+            let old_elements = std::mem::replace(&mut self.elements, new_elements);
+            for (i, elem) in old_elements.into_vec().into_iter().enumerate() {
+                self.elements[i] = elem;
+            }
+        }
+    }
+}
+
+fn measure(name: &str, mut f: impl FnMut()) {
+    const ROUNDS: u32 = 10;
+    let mut best = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        f();
+        best = best.min(start.elapsed());
+    }
+    println!("{:<24} {:>10.3?}", name, best);
+}
+
+fn main() {
+    const N: u64 = 4_000_000;
+
+    measure("OldToyVec<u64>::push", || {
+        let mut v = OldToyVec::new();
+        for i in 0..N {
+            v.push(i);
+        }
+        black_box(&v.elements);
+    });
+    measure("ToyVec<u64>::push", || {
+        let mut v = ToyVec::new();
+        for i in 0..N {
+            v.push(i);
+        }
+        black_box(&v);
+    });
+
+    measure("OldToyVec<String>::push", || {
+        let mut v = OldToyVec::new();
+        for _ in 0..N / 4 {
+            v.push(String::new());
+        }
+        black_box(&v.elements);
+    });
+    measure("ToyVec<String>::push", || {
+        let mut v = ToyVec::new();
+        for _ in 0..N / 4 {
+            v.push(String::new());
+        }
+        black_box(&v);
+    });
+}
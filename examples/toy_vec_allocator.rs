@@ -0,0 +1,80 @@
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::ptr::NonNull;
+use toy_vec::{AllocError, Allocator, Global, ToyVec};
+
+// 確保中のバイト数と、確保・解放の回数を数えるアロケータ
+#[derive(Default)]
+struct Tracking {
+    live_bytes: Cell<usize>,
+    allocations: Cell<usize>,
+    deallocations: Cell<usize>,
+}
+
+unsafe impl Allocator for Tracking {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let ptr = Global.allocate(layout)?;
+        self.live_bytes.set(self.live_bytes.get() + layout.size());
+        self.allocations.set(self.allocations.get() + 1);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout);
+        self.live_bytes.set(self.live_bytes.get() - layout.size());
+        self.deallocations.set(self.deallocations.get() + 1);
+    }
+}
+
+// 借りた領域の先頭から順に切り出すだけで、解放は何もしないアロケータ
+// 領域はBumpの外にあるので、Bumpをムーブしても確保した領域は有効なまま
+struct Bump<'a> {
+    memory: &'a [Cell<u8>],
+    used: Cell<usize>,
+}
+
+unsafe impl<'a> Allocator for Bump<'a> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let base = self.memory.as_ptr() as usize;
+        let start = (base + self.used.get() + layout.align() - 1) & !(layout.align() - 1);
+        let end = start + layout.size();
+        if end > base + self.memory.len() {
+            return Err(AllocError);
+        }
+        self.used.set(end - base);
+        let ptr = self.memory[start - base..].as_ptr() as *mut u8;
+        Ok(NonNull::new(ptr).unwrap())
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+fn main() {
+    // 伸長はアロケータのgrowを経由し、ドロップ時には確保した領域がすべて返却される
+    let tracking = Tracking::default();
+    {
+        let mut v = ToyVec::new_in(&tracking);
+        for i in 0..100u32 {
+            v.push(i);
+        }
+        assert_eq!(tracking.live_bytes.get(), v.capacity() * 4);
+        v.shrink_to_fit();
+        assert_eq!(tracking.live_bytes.get(), 400);
+    }
+    assert_eq!(tracking.live_bytes.get(), 0);
+    assert_eq!(tracking.allocations.get(), tracking.deallocations.get());
+
+    // 固定長の領域を使い切ったら、try_pushはエラーを返す
+    let memory = [(); 256].map(|_| Cell::new(0u8));
+    let bump = Bump {
+        memory: &memory,
+        used: Cell::new(0),
+    };
+    let mut v = ToyVec::with_capacity_in(4, &bump);
+    let mut pushed = 0u64;
+    while v.try_push(pushed).is_ok() {
+        pushed += 1;
+    }
+    assert_eq!(v.len() as u64, pushed);
+    assert_eq!(v.get(3), Some(&3));
+}
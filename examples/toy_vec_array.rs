@@ -0,0 +1,60 @@
+use std::rc::Rc;
+use toy_vec::{ArrayToyVec, CapacityError};
+
+// const fnの中でも要素を追加できる
+const fn primes() -> ArrayToyVec<u32, 4> {
+    let mut v = ArrayToyVec::new();
+    let mut n = 2;
+    while !v.is_full() {
+        let mut d = 2;
+        while d * d <= n && n % d != 0 {
+            d += 1;
+        }
+        if d * d > n {
+            // 満杯でないことはループの条件で確かめている
+            unsafe { v.push_unchecked(n) };
+        }
+        n += 1;
+    }
+    v
+}
+
+// ヒープを使わないので、静的変数もコンパイル時に初期化できる
+static PRIMES: ArrayToyVec<u32, 4> = primes();
+
+fn main() {
+    assert_eq!(PRIMES.as_slice(), &[2, 3, 5, 7]);
+    assert_eq!(PRIMES.get_or(4, &0), &0);
+
+    let mut v: ArrayToyVec<String, 2> = ArrayToyVec::new();
+    assert_eq!(v.capacity(), 2);
+    assert!(v.push("a".to_string()).is_ok());
+    assert!(v.try_push("b".to_string()).is_ok());
+    // 満杯なら追加しようとした値が返ってくる
+    assert_eq!(v.push("c".to_string()), Err(CapacityError("c".to_string())));
+    let err = v.try_push("d".to_string()).unwrap_err();
+    assert_eq!(err.to_string(), "insufficient capacity");
+    assert_eq!(err.element(), "d");
+
+    assert_eq!(v.get(1).map(String::as_str), Some("b"));
+    assert_eq!(v.get(2), None);
+    assert_eq!(v.iter().rev().cloned().collect::<Vec<_>>(), ["b", "a"]);
+    assert_eq!(v.pop().as_deref(), Some("b"));
+    assert_eq!(v.len(), 1);
+
+    // 残った要素はちょうど1回ずつドロップされる
+    let rc = Rc::new(());
+    {
+        let mut v: ArrayToyVec<_, 3> = ArrayToyVec::new();
+        v.push(rc.clone()).unwrap();
+        v.push(rc.clone()).unwrap();
+        let w = v.clone();
+        assert_eq!(Rc::strong_count(&rc), 5);
+        v.clear();
+        assert!(v.is_empty() && w.len() == 2);
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    assert_eq!(format!("{:?}", PRIMES), "[2, 3, 5, 7]");
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::Noisy;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use toy_vec::{FixedIncrement, ToyDeque, ToyVec};
+
+// 領域の終端に0, 1、先頭に折り返して2, 3が並び、panic_idの要素がドロップ時にパニックするToyDeque
+fn wrapped(log: &RefCell<Vec<u32>>, panic_id: u32) -> ToyDeque<Noisy<'_>> {
+    let noisy = |id| {
+        let mut n = common::noisy(id, log);
+        n.panic_on_drop = id == panic_id;
+        n
+    };
+    let mut d = ToyDeque::with_capacity(4);
+    d.push_back(noisy(2));
+    d.push_back(noisy(3));
+    d.push_front(noisy(1));
+    d.push_front(noisy(0));
+    assert_eq!(d.capacity(), 4);
+    assert_eq!(d.as_slices().0.len(), 2);
+    d
+}
+
+fn main() {
+    // 先頭と末尾の両方から出し入れできる
+    let mut d = ToyDeque::with_capacity(4);
+    d.push_back(2);
+    d.push_back(3);
+    d.push_front(1);
+    d.push_front(0);
+    assert_eq!(d.capacity(), 4);
+    // 先頭側は領域の終端に折り返している
+    assert_eq!(d.as_slices(), (&[0, 1][..], &[2, 3][..]));
+    assert_eq!(d.get(2), Some(&2));
+    assert_eq!(d.get_or(4, &-1), &-1);
+    assert_eq!(d.front(), Some(&0));
+    assert_eq!(d.back(), Some(&3));
+    assert_eq!(d.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(d.iter().rev().copied().collect::<Vec<_>>(), [3, 2, 1, 0]);
+
+    // 満杯で折り返した状態から伸長しても順番は保たれる
+    d.push_back(4);
+    assert_eq!(d.capacity(), 8);
+    assert_eq!(d.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    for x in d.iter_mut() {
+        *x *= 10;
+    }
+    assert_eq!(d.pop_front(), Some(0));
+    assert_eq!(d.pop_back(), Some(40));
+    assert_eq!(d.len(), 3);
+
+    // 折り返した部分のほうが短いときも、長いときも正しくつなぎ直す
+    for front in 0..8 {
+        let mut d = ToyDeque::with_capacity(8);
+        for i in (0..front).rev() {
+            d.push_front(i);
+        }
+        for i in front..8 {
+            d.push_back(i);
+        }
+        d.push_back(8);
+        assert_eq!(
+            d.iter().copied().collect::<Vec<_>>(),
+            (0..9).collect::<Vec<_>>()
+        );
+        d.make_contiguous();
+        assert_eq!(d.as_slices().0, &(0..9).collect::<Vec<_>>()[..]);
+    }
+
+    // make_contiguousは領域を確保せずに1つのスライスにまとめる
+    let mut d: ToyDeque<_> = (3..6).collect();
+    d.push_front(2);
+    d.push_front(1);
+    let cap = d.capacity();
+    assert!(!d.as_slices().1.is_empty());
+    assert_eq!(d.make_contiguous(), &[1, 2, 3, 4, 5]);
+    assert!(d.as_slices().1.is_empty());
+    assert_eq!(d.capacity(), cap);
+
+    // ToyVecとの相互変換は領域をそのまま引き継ぐ
+    let v: ToyVec<i32> = (0..5).collect();
+    let ptr = v.as_ptr();
+    let mut d = ToyDeque::from(v);
+    assert_eq!(d.pop_front(), Some(0));
+    d.push_back(5);
+    let v: ToyVec<i32> = d.into();
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+    assert_eq!(v.as_ptr(), ptr);
+
+    // 伸長の方針もToyVecと同じものを使える
+    let v = ToyVec::new().with_growth_policy::<FixedIncrement<3>>();
+    let mut d = ToyDeque::from(v);
+    for i in 0..4 {
+        d.push_front(i);
+    }
+    assert_eq!(d.capacity(), 6);
+    assert_eq!(d.into_iter().rev().collect::<Vec<_>>(), [0, 1, 2, 3]);
+
+    // サイズ0の型でも使える
+    let mut d = ToyDeque::new();
+    d.push_front(());
+    d.push_back(());
+    assert_eq!(d.len(), 2);
+    assert_eq!(d.make_contiguous().len(), 2);
+    assert_eq!(d.pop_back(), Some(()));
+
+    // 折り返した要素もちょうど1回ずつドロップされる
+    let rc = Rc::new(());
+    {
+        let mut d = ToyDeque::with_capacity(4);
+        for _ in 0..3 {
+            d.push_front(rc.clone());
+            d.push_back(rc.clone());
+        }
+        let c = d.clone();
+        assert_eq!(Rc::strong_count(&rc), 13);
+        let mut it = c.into_iter();
+        it.next();
+        it.next_back();
+        drop(it);
+        assert_eq!(Rc::strong_count(&rc), 7);
+        d.pop_front();
+        d.clear();
+        assert!(d.is_empty());
+        d.push_back(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    // 折り返しの前の要素のデストラクタがパニックしても、後ろの要素はドロップされる
+    panic::set_hook(Box::new(|_| {})); // 意図したパニックなのでメッセージは表示しない
+    let log = RefCell::new(Vec::new());
+    let d = wrapped(&log, 0);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(d))).is_err());
+    assert_eq!(*log.borrow(), [0, 1, 2, 3]);
+
+    // clearでも同様。パニックした後も空のToyDequeとして使える
+    let log = RefCell::new(Vec::new());
+    let mut d = wrapped(&log, 1);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| d.clear())).is_err());
+    assert_eq!(*log.borrow(), [0, 1, 2, 3]);
+    assert!(d.is_empty());
+    d.push_back(Noisy {
+        id: 4,
+        log: &log,
+        panic_on_drop: false,
+    });
+    drop(d);
+    assert_eq!(*log.borrow(), [0, 1, 2, 3, 4]);
+
+    // 途中まで消費したDequeIntoIterのドロップでも同様
+    let log = RefCell::new(Vec::new());
+    let mut it = wrapped(&log, 1).into_iter();
+    assert_eq!(it.next_back().map(|n| n.id), Some(3));
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(it))).is_err());
+    assert_eq!(*log.borrow(), [3, 0, 1, 2]);
+    let _ = panic::take_hook();
+
+    assert_eq!(
+        format!("{:?}", (1..4).collect::<ToyDeque<_>>()),
+        "[1, 2, 3]"
+    );
+}
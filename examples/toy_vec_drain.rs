@@ -0,0 +1,71 @@
+mod common;
+
+use common::{dropped, ids, noisy, noisy_vec};
+use std::cell::RefCell;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+
+fn main() {
+    panic::set_hook(Box::new(|_| {})); // 意図したパニックなのでメッセージは表示しない
+
+    // 最後まで消費しなくても、残りの要素はDrainのドロップでドロップされ、後ろが詰められる
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..6, &log);
+        let mut drain = v.drain(1..4);
+        assert_eq!(drain.next().map(|n| n.id), Some(1));
+        assert_eq!(drain.next_back().map(|n| n.id), Some(3));
+        drop(drain);
+        assert_eq!(ids(&v), [0, 4, 5]);
+        assert_eq!(dropped(&log), [1, 2, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5]);
+
+    // mem::forgetされても、ToyVecは範囲より前の要素だけを持つ有効な状態で残る
+    // 範囲と後ろの要素はリークし、ドロップされない
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..6, &log);
+        mem::forget(v.drain(2..4));
+        assert_eq!(v.len(), 2);
+        assert_eq!(ids(&v), [0, 1]);
+        assert!(log.borrow().is_empty());
+        // 残った状態のまま使い続けられる
+        v.push(noisy(6, &log));
+        assert_eq!(ids(&v), [0, 1, 6]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 6]);
+
+    // 要素を受け取る側が途中でパニックしても、Drainは巻き戻しの中でドロップされ、
+    // 残りの要素をドロップして後ろを詰める
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..6, &log);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            for n in v.drain(1..5) {
+                if n.id == 2 {
+                    panic!("consumer panicked");
+                }
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(v.len(), 2);
+        assert_eq!(ids(&v), [0, 5]);
+        assert_eq!(dropped(&log), [1, 2, 3, 4]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5]);
+
+    // 残りの要素のデストラクタがパニックしても、他の要素はドロップされ後ろも詰められる
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..6, &log);
+        v[2].panic_on_drop = true;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            v.drain(1..4);
+        }));
+        assert!(result.is_err());
+        assert_eq!(ids(&v), [0, 4, 5]);
+        assert_eq!(dropped(&log), [1, 2, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5]);
+}
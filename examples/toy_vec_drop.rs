@@ -0,0 +1,34 @@
+mod common;
+
+use common::{noisy, panicky};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use toy_vec::ToyVec;
+
+fn main() {
+    // 生存している要素だけが、先頭から順にちょうど1回ずつドロップされる
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = ToyVec::new();
+        for id in 0..5 {
+            v.push(noisy(id, &log));
+        }
+        let popped = v.pop();
+        assert_eq!(*log.borrow(), Vec::<u32>::new()); // popで取り出しただけではドロップされない
+        drop(popped);
+        assert_eq!(*log.borrow(), vec![4]);
+    }
+    assert_eq!(*log.borrow(), vec![4, 0, 1, 2, 3]);
+
+    // 途中の要素のデストラクタがパニックしても、残りの要素はドロップされる
+    let log = RefCell::new(Vec::new());
+    panic::set_hook(Box::new(|_| {})); // 意図したパニックなのでメッセージは表示しない
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut v = ToyVec::new();
+        v.push(noisy(0, &log));
+        v.push(panicky(1, &log));
+        v.push(noisy(2, &log));
+    }));
+    assert!(result.is_err());
+    assert_eq!(*log.borrow(), vec![0, 1, 2]);
+}
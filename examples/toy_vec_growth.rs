@@ -0,0 +1,44 @@
+use toy_vec::{Doubling, FixedIncrement, GrowthPolicy, OneAndHalf, SizeClass, ToyVec};
+
+// 1つずつpushしたとき、容量が変わるたびにその値を記録する
+fn capacities<G: GrowthPolicy>(count: usize) -> Vec<usize> {
+    let mut v = ToyVec::<u32>::new().with_growth_policy::<G>();
+    let mut caps = Vec::new();
+    for i in 0..count {
+        v.push(i as u32);
+        if caps.last() != Some(&v.capacity()) {
+            caps.push(v.capacity());
+        }
+    }
+    caps
+}
+
+fn main() {
+    assert_eq!(capacities::<Doubling>(20), [1, 2, 4, 8, 16, 32]);
+    assert_eq!(capacities::<OneAndHalf>(20), [1, 2, 3, 4, 6, 9, 13, 19, 28]);
+    assert_eq!(capacities::<FixedIncrement<8>>(20), [8, 16, 24]);
+    // u32は4バイトなので、1ページに1024個入る
+    assert_eq!(
+        capacities::<SizeClass>(2000),
+        [1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048]
+    );
+    // 直接呼ばれても、サイズ0の要素で0除算しない
+    assert_eq!(SizeClass::grow(2, 3, 0), 4);
+
+    // reserveやextendも同じ方針で伸長する
+    let mut v = ToyVec::<u8>::new().with_growth_policy::<SizeClass>();
+    v.reserve(5000);
+    assert_eq!(v.capacity(), 8192);
+    let mut v = ToyVec::<u8>::new().with_growth_policy::<FixedIncrement<100>>();
+    v.extend(0..10);
+    assert_eq!(v.capacity(), 100);
+    v.extend(0..100);
+    assert_eq!(v.capacity(), 200);
+
+    // 方針を変えても要素と容量はそのまま
+    let v: ToyVec<_> = (0..5).collect();
+    let cap = v.capacity();
+    let v2 = v.clone().with_growth_policy::<OneAndHalf>();
+    assert_eq!(v2.capacity(), cap);
+    assert_eq!(v2, v);
+}
@@ -0,0 +1,16 @@
+use toy_vec::{toy_vec, ToyVec};
+
+fn main() {
+    let v = toy_vec!["Java Finch".to_string(), "Budgerigar".to_string()];
+    assert_eq!(v.get(1), Some(&"Budgerigar".to_string()));
+    assert_eq!(v.capacity(), 2);
+
+    // 繰り返しの形式では、要素はクローンされる
+    let v = toy_vec![String::from("Canary"); 3];
+    assert_eq!(v, ["Canary", "Canary", "Canary"]);
+    assert_eq!(v.capacity(), 3);
+
+    let v: ToyVec<i32> = toy_vec![];
+    assert!(v.is_empty());
+    assert_eq!(v.capacity(), 0);
+}
@@ -0,0 +1,44 @@
+// ポインタを使った低レベルの操作の例
+// 未定義動作がないことは ci/miri.sh（cargo +nightly miri run --example toy_vec_raw_parts）で確認している
+use std::mem::MaybeUninit;
+use toy_vec::{toy_vec, ToyVec};
+
+// read(2)のように、渡された領域の先頭から書き込んだバイト数を返す関数
+fn fill(buf: &mut [MaybeUninit<u8>]) -> usize {
+    let data = b"Java Finch";
+    let n = data.len().min(buf.len());
+    for (slot, byte) in buf.iter_mut().zip(data) {
+        slot.write(*byte);
+    }
+    n
+}
+
+fn main() {
+    // 未初期化の領域に書き込んでから、その分だけ長さを伸ばす
+    let mut v = ToyVec::with_capacity(16);
+    v.push(b'>');
+    let n = fill(v.spare_capacity_mut());
+    unsafe { v.set_len(v.len() + n) };
+    assert_eq!(v, *b">Java Finch");
+    assert_eq!(v.spare_capacity_mut().len(), 16 - v.len());
+
+    // 分解して元に戻しても、同じ領域と要素のまま
+    let v = toy_vec![String::from("Budgerigar"), String::from("Canary")];
+    let ptr_before = v.as_ptr();
+    let (ptr, len, cap) = v.into_raw_parts();
+    assert_eq!(ptr as *const String, ptr_before);
+    let v = unsafe { ToyVec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(v, ["Budgerigar", "Canary"]);
+
+    // Vecから取り出したポインタも受け取れる
+    let mut from_vec = std::mem::ManuallyDrop::new(vec![1u32, 2, 3]);
+    let v = unsafe {
+        ToyVec::from_raw_parts(from_vec.as_mut_ptr(), from_vec.len(), from_vec.capacity())
+    };
+    assert_eq!(v, [1, 2, 3]);
+
+    // 空のToyVecでもポインタはnullにならない
+    let mut empty: ToyVec<u64> = ToyVec::new();
+    assert!(!empty.as_mut_ptr().is_null());
+    assert!(empty.spare_capacity_mut().is_empty());
+}
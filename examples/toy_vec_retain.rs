@@ -0,0 +1,102 @@
+mod common;
+
+use common::{dropped, ids, noisy_vec};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+fn main() {
+    panic::set_hook(Box::new(|_| {})); // 意図したパニックなのでメッセージは表示しない
+
+    // retainの述語が途中でパニックしても、調べ終えた分の結果と未処理の要素が前に詰めて残る
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..8, &log);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            v.retain(|n| {
+                if n.id == 5 {
+                    panic!("predicate panicked");
+                }
+                n.id % 2 == 0
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(ids(&v), [0, 2, 4, 5, 6, 7]);
+        assert_eq!(dropped(&log), [1, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    // dedup_byの比較が途中でパニックしても同様
+    // idを2で割った値が同じ要素を重複とみなす
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..8, &log);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            v.dedup_by(|a, b| {
+                if a.id == 5 {
+                    panic!("same_bucket panicked");
+                }
+                a.id / 2 == b.id / 2
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(ids(&v), [0, 2, 4, 5, 6, 7]);
+        assert_eq!(dropped(&log), [1, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    // extract_ifの述語が途中でパニックしても、取り出し済みの要素は呼び出し側が持ち、
+    // 未処理の要素と範囲の後ろの要素は前に詰めて残る
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..8, &log);
+        let mut extracted = Vec::new();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let iter = v.extract_if(1..7, |n| {
+                if n.id == 5 {
+                    panic!("predicate panicked");
+                }
+                n.id % 2 == 1
+            });
+            for n in iter {
+                extracted.push(n);
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(extracted.iter().map(|n| n.id).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(ids(&v), [0, 2, 4, 5, 6, 7]);
+        assert!(log.borrow().is_empty());
+        drop(extracted);
+        assert_eq!(dropped(&log), [1, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    // extract_ifは範囲外の要素を調べない
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..8, &log);
+        let mut seen = Vec::new();
+        let extracted: Vec<_> = v
+            .extract_if(2..6, |n| {
+                seen.push(n.id);
+                n.id % 2 == 1
+            })
+            .collect();
+        assert_eq!(seen, [2, 3, 4, 5]);
+        assert_eq!(extracted.iter().map(|n| n.id).collect::<Vec<_>>(), [3, 5]);
+        assert_eq!(ids(&v), [0, 1, 2, 4, 6, 7]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    // 途中でドロップすると、調べていない要素は述語にかけずにそのまま残る
+    let log = RefCell::new(Vec::new());
+    {
+        let mut v = noisy_vec(0..8, &log);
+        let mut iter = v.extract_if(.., |n| n.id % 2 == 1);
+        assert_eq!(iter.next().map(|n| n.id), Some(1));
+        assert_eq!(iter.next().map(|n| n.id), Some(3));
+        drop(iter);
+        assert_eq!(ids(&v), [0, 2, 4, 5, 6, 7]);
+        assert_eq!(dropped(&log), [1, 3]);
+    }
+    assert_eq!(dropped(&log), [0, 1, 2, 3, 4, 5, 6, 7]);
+}
@@ -0,0 +1,59 @@
+use std::rc::Rc;
+use toy_vec::SmallToyVec;
+
+fn main() {
+    let mut v: SmallToyVec<i32, 4> = SmallToyVec::new();
+    assert_eq!(v.capacity(), 4);
+    for i in 0..4 {
+        v.push(i);
+    }
+    // 4個までは配列に格納される
+    assert!(!v.spilled());
+    assert_eq!(v.get(3), Some(&3));
+    assert_eq!(v.get_or(4, &-1), &-1);
+
+    // 5個目でヒープに移る
+    v.push(4);
+    assert!(v.spilled());
+    assert_eq!(v.capacity(), 8);
+    assert_eq!(v.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+    // 要素が減っても、shrink_to_fitを呼ぶまではヒープのまま
+    assert_eq!(v.pop(), Some(4));
+    assert!(v.spilled());
+    v.shrink_to_fit();
+    assert!(!v.spilled());
+    assert_eq!(&*v, &[0, 1, 2, 3]);
+
+    // 配列に収まらない要素数ならshrink_to_fitは容量を縮めるだけ
+    v.extend(4..10);
+    assert!(v.spilled());
+    v.shrink_to_fit();
+    assert!(v.spilled());
+    assert_eq!(v.capacity(), 10);
+    while v.pop().is_some() {}
+    assert!(v.is_empty());
+
+    // 配列とヒープのどちらにある要素も、ちょうど1回ずつドロップされる
+    let rc = Rc::new(());
+    {
+        let mut v: SmallToyVec<_, 2> = SmallToyVec::new();
+        v.push(rc.clone());
+        v.push(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 3);
+        let w = v.clone();
+        v.push(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 6);
+        drop(v.pop());
+        v.shrink_to_fit();
+        assert!(!v.spilled() && !w.spilled());
+        assert_eq!(Rc::strong_count(&rc), 5);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    // サイズ0の配列でも使える
+    let mut v: SmallToyVec<String, 0> = SmallToyVec::new();
+    v.push("a".to_string());
+    assert!(v.spilled());
+    assert_eq!(format!("{:?}", v), r#"["a"]"#);
+}
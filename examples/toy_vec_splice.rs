@@ -0,0 +1,105 @@
+mod common;
+
+use common::{ids, noisy, noisy_vec, take_dropped};
+use std::cell::RefCell;
+
+fn main() {
+    let log = RefCell::new(Vec::new());
+
+    // 範囲の後ろに要素がなければ、置き換える要素は末尾に追加するだけ
+    let mut v = noisy_vec(0..5, &log);
+    let removed: Vec<_> = v.splice(3.., (10..14).map(|id| noisy(id, &log))).collect();
+    assert_eq!(ids(&removed), [3, 4]);
+    assert_eq!(ids(&v), [0, 1, 2, 10, 11, 12, 13]);
+    drop(removed);
+    assert_eq!(take_dropped(&log), [3, 4]);
+    drop(v);
+    assert_eq!(take_dropped(&log), [0, 1, 2, 10, 11, 12, 13]);
+
+    // 取り除く範囲より短い、同じ長さ、長い置き換え
+    // 長い場合はsize_hintの下限の分だけ後ろの要素をずらす
+    // 返さなかった要素はSpliceのドロップでドロップされる
+    for (count, expected) in [
+        (1, vec![0, 10, 4, 5]),
+        (3, vec![0, 10, 11, 12, 4, 5]),
+        (5, vec![0, 10, 11, 12, 13, 14, 4, 5]),
+    ] {
+        let mut v = noisy_vec(0..6, &log);
+        drop(v.splice(1..4, (10..10 + count).map(|id| noisy(id, &log))));
+        assert_eq!(ids(&v), expected);
+        assert_eq!(take_dropped(&log), [1, 2, 3]);
+        drop(v);
+        let mut expected = expected;
+        expected.sort_unstable();
+        assert_eq!(take_dropped(&log), expected);
+    }
+
+    // filterで下限が0になるイテレータでも同じ結果になる
+    // 跡を埋めきれなかった残りは、いったん集めてから後ろの要素をずらす
+    for (count, expected) in [
+        (1, vec![0, 10, 4, 5]),
+        (3, vec![0, 10, 11, 12, 4, 5]),
+        (5, vec![0, 10, 11, 12, 13, 14, 4, 5]),
+    ] {
+        let mut v = noisy_vec(0..6, &log);
+        let replace_with = (10..10 + count).map(|id| noisy(id, &log)).filter(|_| true);
+        assert_eq!(replace_with.size_hint().0, 0);
+        let mut splice = v.splice(1..4, replace_with);
+        assert_eq!(splice.next().map(|n| n.id), Some(1));
+        drop(splice);
+        assert_eq!(ids(&v), expected);
+        assert_eq!(take_dropped(&log), [1, 2, 3]);
+        drop(v);
+        let mut expected = expected;
+        expected.sort_unstable();
+        assert_eq!(take_dropped(&log), expected);
+    }
+
+    // split_offは後ろの要素をムーブするだけで、ドロップもクローンもしない
+    let mut v = noisy_vec(0..5, &log);
+    let w = v.split_off(2);
+    assert_eq!((ids(&v), ids(&w)), (vec![0, 1], vec![2, 3, 4]));
+    assert!(v.split_off(2).is_empty());
+    let all = v.split_off(0);
+    assert!(v.is_empty());
+    assert_eq!(ids(&all), [0, 1]);
+    assert!(log.borrow().is_empty());
+    drop((v, w, all));
+    assert_eq!(take_dropped(&log), [0, 1, 2, 3, 4]);
+
+    // appendはotherの要素をムーブし、otherを空にする
+    let mut a = noisy_vec(0..2, &log);
+    let mut b = noisy_vec(2..4, &log);
+    a.append(&mut b);
+    assert_eq!(ids(&a), [0, 1, 2, 3]);
+    assert!(b.is_empty());
+    drop(b);
+    assert!(log.borrow().is_empty());
+    drop(a);
+    assert_eq!(take_dropped(&log), [0, 1, 2, 3]);
+
+    // resizeは伸ばすときvalueのクローンで埋め、縮めるときは後ろの要素とvalueをドロップする
+    let mut v = noisy_vec(0..3, &log);
+    v.resize(5, noisy(9, &log));
+    assert_eq!(ids(&v), [0, 1, 2, 9, 9]);
+    assert!(log.borrow().is_empty());
+    v.resize(2, noisy(8, &log));
+    assert_eq!(ids(&v), [0, 1]);
+    assert_eq!(take_dropped(&log), [2, 8, 9, 9]);
+    drop(v);
+    assert_eq!(take_dropped(&log), [0, 1]);
+
+    // resize_withは伸ばすときだけfを呼ぶ
+    let mut next = 20;
+    let mut v = noisy_vec(0..2, &log);
+    v.resize_with(4, || {
+        next += 1;
+        noisy(next - 1, &log)
+    });
+    assert_eq!(ids(&v), [0, 1, 20, 21]);
+    v.resize_with(1, || unreachable!());
+    assert_eq!(ids(&v), [0]);
+    assert_eq!(take_dropped(&log), [1, 20, 21]);
+    drop(v);
+    assert_eq!(take_dropped(&log), [0]);
+}
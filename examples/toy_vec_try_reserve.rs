@@ -0,0 +1,31 @@
+use toy_vec::{ToyVec, TryReserveErrorKind};
+
+fn main() {
+    let mut v: ToyVec<u64> = ToyVec::new();
+    v.push(1);
+
+    // 要素数の計算が溢れる場合
+    let err = v.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+
+    // バイト数がisize::MAXを超える場合
+    let err = v.try_reserve_exact(usize::MAX / 4).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+
+    // アロケータが確保に失敗した場合は、要求したレイアウトがわかる
+    match ToyVec::<u8>::try_with_capacity(isize::MAX as usize) {
+        Err(err) => match err.kind() {
+            TryReserveErrorKind::AllocError { layout } => {
+                assert_eq!(layout.size(), isize::MAX as usize);
+                println!("{}", err);
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        },
+        Ok(_) => println!("the allocator handed out isize::MAX bytes"),
+    }
+
+    // 失敗してもToyVecはそのまま使える
+    assert_eq!(v.len(), 1);
+    assert!(v.try_push(2).is_ok());
+    assert_eq!(v.get(1), Some(&2));
+}
@@ -0,0 +1,65 @@
+use std::alloc::Layout;
+use std::panic;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use toy_vec::{AllocError, Allocator, ToyVec};
+
+// 確保を要求されたらパニックするアロケータ
+struct NoAlloc;
+
+unsafe impl Allocator for NoAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        panic!("unexpected allocation: {:?}", layout);
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, layout: Layout) {
+        panic!("unexpected deallocation: {:?}", layout);
+    }
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+// ドロップされた回数を数えるサイズ0の型
+struct Marker;
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn main() {
+    // サイズ0の型では領域を確保せず、容量は常にusize::MAX
+    let mut v = ToyVec::new_in(NoAlloc);
+    assert_eq!(v.capacity(), usize::MAX);
+    for _ in 0..1000 {
+        v.push(Marker);
+    }
+    v.reserve(1 << 40);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), usize::MAX);
+    assert!(v.pop().is_some());
+    v.insert(10, Marker);
+    v.truncate(500);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 501);
+    let mut it = v.into_iter();
+    assert_eq!(it.len(), 500);
+    assert!(it.nth(99).is_some());
+    assert!(it.next_back().is_some());
+    drop(it);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1001);
+
+    // usize::MAX個まで数えられる
+    let mut v: ToyVec<()> = ToyVec::new();
+    unsafe { v.set_len(usize::MAX - 1) };
+    v.push(());
+    assert_eq!(v.len(), usize::MAX);
+    assert_eq!(v.iter().len(), usize::MAX);
+    assert_eq!(v.iter().rev().nth(usize::MAX - 1), Some(&()));
+    assert!(v.try_push(()).is_err());
+    panic::set_hook(Box::new(|_| {}));
+    let overflow = panic::catch_unwind(panic::AssertUnwindSafe(|| v.push(())));
+    assert!(overflow.is_err());
+    assert_eq!(v.pop(), Some(()));
+    assert_eq!(v.len(), usize::MAX - 1);
+}
@@ -0,0 +1,142 @@
+use alloc::alloc::{alloc, dealloc, realloc};
+use core::alloc::Layout;
+use core::fmt;
+use core::ptr::{self, NonNull};
+
+// アロケータが領域を確保できなかったことを表すエラー
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+/// ToyVecが領域の確保と解放に使うアロケータ
+/// 標準ライブラリのAllocatorトレイトはnightlyでしか使えないので、安定版で使える最小限のものを用意する
+/// ToyVecはサイズ0のレイアウトでこれらのメソッドを呼ぶことはない
+///
+/// # Safety
+///
+/// 実装は以下を守らなければならない
+///
+/// - `allocate`や`grow`、`shrink`が返した領域は、`deallocate`されるか、
+///   `grow`や`shrink`に渡されるまで有効で、他の領域と重ならないこと
+/// - アロケータをムーブしても、それまでに確保した領域は有効なままであること
+pub unsafe trait Allocator {
+    // layoutで指定された大きさとアラインメントを満たす未初期化の領域を確保する
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// # Safety
+    ///
+    /// `ptr`はこのアロケータで`layout`を指定して確保した領域でなければならない
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// 領域をnew_layoutの大きさに広げる。内容はold_layoutの大きさの分だけ引き継がれる
+    /// デフォルトでは新しい領域を確保してコピーする。その場で広げられるアロケータは上書きするとよい
+    ///
+    /// # Safety
+    ///
+    /// `ptr`はこのアロケータで`old_layout`を指定して確保した領域で、
+    /// `new_layout`は`old_layout`と同じアラインメントで、大きさが`old_layout`以上でなければならない
+    /// 成功したら`ptr`は無効になる
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    /// 領域をnew_layoutの大きさに縮める。内容はnew_layoutの大きさの分だけ引き継がれる
+    ///
+    /// # Safety
+    ///
+    /// `ptr`はこのアロケータで`old_layout`を指定して確保した領域で、
+    /// `new_layout`は`old_layout`と同じアラインメントで、大きさが`old_layout`以下でなければならない
+    /// 成功したら`ptr`は無効になる
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        debug_assert!(new_layout.size() <= old_layout.size());
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), new_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+}
+
+// アロケータへの参照もアロケータとして使えるようにする
+// これにより、アリーナなどをToyVecに所有させずに共有できる
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        (**self).shrink(ptr, old_layout, new_layout)
+    }
+}
+
+// グローバルアロケータ。ToyVecのデフォルトのアロケータ
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        NonNull::new(unsafe { alloc(layout) }).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        dealloc(ptr.as_ptr(), layout)
+    }
+
+    // reallocを使い、できるならその場で広げる
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        NonNull::new(realloc(ptr.as_ptr(), old_layout, new_layout.size())).ok_or(AllocError)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        NonNull::new(realloc(ptr.as_ptr(), old_layout, new_layout.size())).ok_or(AllocError)
+    }
+}
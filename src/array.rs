@@ -0,0 +1,213 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::{ptr, slice};
+
+use crate::error::CapacityError;
+use crate::{Iter, IterMut};
+
+// 容量がコンパイル時にN個で決まり、ヒープの領域を一切使わないToyVec
+// 要素は自身の中の配列に格納するので、allocクレートのない環境でも使える
+//
+// 領域を確保しないので、満杯のときのpushは要素を追加せずにエラーを返す
+// 多くのメソッドはconst fnなので、定数や静的変数の初期化にも使える
+pub struct ArrayToyVec<T, const N: usize> {
+    // 先頭からlen個の要素だけが初期化済み
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayToyVec<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            // MaybeUninitの配列は未初期化のままで有効な値
+            buf: unsafe { MaybeUninit::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // 満杯ならelementをCapacityErrorに包んで返し、ArrayToyVecは変更しない
+    pub const fn push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError(element));
+        }
+        unsafe { self.push_unchecked(element) };
+        Ok(())
+    }
+
+    // pushと同じ。ToyVecのtry_pushと同じ名前でも呼べるようにしている
+    pub const fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.push(element)
+    }
+
+    /// 容量を調べずに要素を追加する
+    ///
+    /// # Safety
+    ///
+    /// 満杯でない（`len() < capacity()`である）こと
+    pub const unsafe fn push_unchecked(&mut self, element: T) {
+        debug_assert!(self.len < N);
+        // 未初期化のスロットに書き込むので、古い値をドロップしないptr::writeを使う
+        ptr::write(self.as_mut_ptr().add(self.len), element);
+        self.len += 1;
+    }
+
+    /// 長さを`new_len`に変える。要素の初期化やドロップは行わない
+    ///
+    /// # Safety
+    ///
+    /// - `new_len`は`capacity()`以下であること
+    /// - `old_len..new_len`の要素は初期化済みであること
+    /// - 長さを縮める場合、`new_len..old_len`の要素のドロップは呼び出し側で行うこと
+    pub const unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= N);
+        self.len = new_len;
+    }
+
+    pub const fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            // 値を読み出したスロットは未初期化として扱う
+            Some(unsafe { ptr::read(self.as_ptr().add(self.len)) })
+        }
+    }
+
+    // 全要素をドロップする。要素のドロップはconst fnでは行えない
+    pub fn clear(&mut self) {
+        let elems: *mut [T] = self.as_mut_slice();
+        // ドロップ中にパニックしても二重にドロップしないよう、先に長さを0にする
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elems) };
+    }
+
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr() as *const T
+    }
+
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr() as *mut T
+    }
+
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    pub const fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { &*self.as_ptr().add(index) })
+        } else {
+            None
+        }
+    }
+
+    pub const fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { &mut *self.as_mut_ptr().add(index) })
+        } else {
+            None
+        }
+    }
+
+    pub const fn get_or<'a>(&'a self, index: usize, default: &'a T) -> &'a T {
+        match self.get(index) {
+            Some(elem) => elem,
+            None => default,
+        }
+    }
+
+    // ToyVecと同じイテレータを返す
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            elements: self.as_slice(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            elements: self.as_mut_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayToyVec<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T, const N: usize> Default for ArrayToyVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayToyVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayToyVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayToyVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut v = Self::new();
+        for elem in self.iter() {
+            // 要素数はself以下なので満杯にはならない
+            unsafe { v.push_unchecked(elem.clone()) };
+        }
+        v
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayToyVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'vec, T, const N: usize> IntoIterator for &'vec ArrayToyVec<T, N> {
+    type Item = &'vec T;
+    type IntoIter = Iter<'vec, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'vec, T, const N: usize> IntoIterator for &'vec mut ArrayToyVec<T, N> {
+    type Item = &'vec mut T;
+    type IntoIter = IterMut<'vec, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
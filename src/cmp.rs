@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::allocator::Allocator;
+use crate::growth::GrowthPolicy;
+use crate::ToyVec;
+
+// ToyVecと、スライスやVecなどの要素の列との比較は、すべて初期化済みの要素のスライス同士の比較にする
+macro_rules! impl_slice_eq {
+    ([$($vars:tt)*] $lhs:ty, $rhs:ty) => {
+        impl<T, U, $($vars)*> PartialEq<$rhs> for $lhs
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &$rhs) -> bool {
+                self[..] == other[..]
+            }
+        }
+    };
+}
+
+impl_slice_eq! { [A1: Allocator, G1: GrowthPolicy, A2: Allocator, G2: GrowthPolicy] ToyVec<T, A1, G1>, ToyVec<U, A2, G2> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] ToyVec<T, A, G>, [U] }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] ToyVec<T, A, G>, &[U] }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] ToyVec<T, A, G>, &mut [U] }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy, const N: usize] ToyVec<T, A, G>, [U; N] }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy, const N: usize] ToyVec<T, A, G>, &[U; N] }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] ToyVec<T, A, G>, Vec<U> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] [T], ToyVec<U, A, G> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] &[T], ToyVec<U, A, G> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] &mut [T], ToyVec<U, A, G> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy, const N: usize] [T; N], ToyVec<U, A, G> }
+impl_slice_eq! { [A: Allocator, G: GrowthPolicy] Vec<T>, ToyVec<U, A, G> }
+
+impl<T: Eq, A: Allocator, G: GrowthPolicy> Eq for ToyVec<T, A, G> {}
+
+// 辞書式順序で比較する
+impl<T, A1, G1, A2, G2> PartialOrd<ToyVec<T, A2, G2>> for ToyVec<T, A1, G1>
+where
+    T: PartialOrd,
+    A1: Allocator,
+    G1: GrowthPolicy,
+    A2: Allocator,
+    G2: GrowthPolicy,
+{
+    fn partial_cmp(&self, other: &ToyVec<T, A2, G2>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, A: Allocator, G: GrowthPolicy> Ord for ToyVec<T, A, G> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::boxed::Box;
+use alloc::string::{FromUtf8Error, String};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+use crate::allocator::{Allocator, Global};
+use crate::growth::GrowthPolicy;
+use crate::raw::RawToyVec;
+use crate::ToyVec;
+
+// VecもToyVecもグローバルアロケータからT型の配列として領域を確保するので、
+// 要素をコピーせずに領域の所有権をそのまま受け渡せる
+impl<T> From<Vec<T>> for ToyVec<T> {
+    fn from(v: Vec<T>) -> Self {
+        let mut v = ManuallyDrop::new(v);
+        let (ptr, len, cap) = (v.as_mut_ptr(), v.len(), v.capacity());
+        let buf = unsafe { RawToyVec::from_raw_parts_in(ptr, cap, Global) };
+        ToyVec::from_buf(buf, len)
+    }
+}
+
+impl<T> From<ToyVec<T>> for Vec<T> {
+    fn from(v: ToyVec<T>) -> Self {
+        let (buf, len) = v.into_raw_buf();
+        let buf = ManuallyDrop::new(buf);
+        unsafe { Vec::from_raw_parts(buf.ptr(), len, buf.capacity()) }
+    }
+}
+
+// Box<[T]>は要素数ちょうどの配列として確保されているので、その領域をそのまま使う
+impl<T> From<Box<[T]>> for ToyVec<T> {
+    fn from(b: Box<[T]>) -> Self {
+        let len = b.len();
+        let ptr = Box::into_raw(b) as *mut T;
+        let buf = unsafe { RawToyVec::from_raw_parts_in(ptr, len, Global) };
+        ToyVec::from_buf(buf, len)
+    }
+}
+
+impl<T> From<ToyVec<T>> for Box<[T]> {
+    fn from(v: ToyVec<T>) -> Self {
+        v.into_boxed_slice()
+    }
+}
+
+impl<T> ToyVec<T> {
+    // 余分な容量を返却してから、領域をBox<[T]>として引き渡す
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        self.shrink_to_fit();
+        let (buf, len) = self.into_raw_buf();
+        let buf = ManuallyDrop::new(buf);
+        unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buf.ptr(), len)) }
+    }
+}
+
+// 配列の要素を一度にムーブする。確保は1回だけ
+impl<T, const N: usize> From<[T; N]> for ToyVec<T> {
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        let mut v = ToyVec::with_capacity(N);
+        unsafe {
+            ptr::copy_nonoverlapping(array.as_ptr(), v.buf.ptr(), N);
+        }
+        v.len = N;
+        v
+    }
+}
+
+// 長さがちょうどNのときだけ配列に変換できる。そうでなければ元のToyVecをそのまま返す
+impl<T, A: Allocator, G: GrowthPolicy, const N: usize> TryFrom<ToyVec<T, A, G>> for [T; N] {
+    type Error = ToyVec<T, A, G>;
+
+    fn try_from(mut v: ToyVec<T, A, G>) -> Result<Self, Self::Error> {
+        if v.len != N {
+            return Err(v);
+        }
+        // 要素は配列へムーブするので、vのドロップでは領域の解放だけを行わせる
+        v.len = 0;
+        Ok(unsafe { ptr::read(v.buf.ptr() as *const [T; N]) })
+    }
+}
+
+impl<T: Clone> From<&[T]> for ToyVec<T> {
+    fn from(s: &[T]) -> Self {
+        let mut v = ToyVec::new();
+        v.extend_from_slice(s);
+        v
+    }
+}
+
+impl<T: Clone> From<&mut [T]> for ToyVec<T> {
+    fn from(s: &mut [T]) -> Self {
+        ToyVec::from(&*s)
+    }
+}
+
+impl<T: Clone, const N: usize> From<&[T; N]> for ToyVec<T> {
+    fn from(s: &[T; N]) -> Self {
+        ToyVec::from(&s[..])
+    }
+}
+
+// 文字列はUTF-8のバイト列として変換する
+impl From<&str> for ToyVec<u8> {
+    fn from(s: &str) -> Self {
+        ToyVec::from(s.as_bytes())
+    }
+}
+
+impl From<String> for ToyVec<u8> {
+    fn from(s: String) -> Self {
+        ToyVec::from(s.into_bytes())
+    }
+}
+
+// 正しいUTF-8でなければエラーを返す。エラーからは元のバイト列を取り出せる
+impl TryFrom<ToyVec<u8>> for String {
+    type Error = FromUtf8Error;
+
+    fn try_from(v: ToyVec<u8>) -> Result<Self, Self::Error> {
+        String::from_utf8(Vec::from(v))
+    }
+}
@@ -0,0 +1,490 @@
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::{ptr, slice};
+
+use crate::allocator::{Allocator, Global};
+use crate::error::TryReserveError;
+use crate::growth::{Doubling, GrowthPolicy};
+use crate::raw::{handle_reserve, RawToyVec};
+use crate::{Iter, IterMut, ToyVec};
+
+// ToyVecと同じ領域を環状に使う両端キュー
+// 先頭からも末尾からもO(1)で要素を出し入れできる
+//
+// 要素はheadから始まり、領域の終端に達したら先頭に折り返して続く
+// 領域の確保や伸長の方針はToyVecと同じものを使う
+pub struct ToyDeque<T, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    buf: RawToyVec<T, A>,
+    // 先頭の要素がある位置。容量が0でなければ常に容量未満
+    head: usize,
+    // headから折り返しを含めてlen個の要素だけが初期化済み
+    len: usize,
+    growth: PhantomData<fn() -> G>,
+}
+
+impl<T> ToyDeque<T> {
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: Allocator> ToyDeque<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        Self::from_buf(RawToyVec::new_in(alloc), 0, 0)
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::from_buf(RawToyVec::with_capacity_in(capacity, alloc), 0, 0)
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> ToyDeque<T, A, G> {
+    fn from_buf(buf: RawToyVec<T, A>, head: usize, len: usize) -> Self {
+        Self {
+            buf,
+            head,
+            len,
+            growth: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve(additional));
+    }
+
+    // 容量を広げたら、折り返していた要素を新しい領域の中でつなぎ直す
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let old_capacity = self.capacity();
+        self.buf.try_reserve::<G>(self.len, additional)?;
+        if self.capacity() > old_capacity {
+            self.handle_capacity_increase(old_capacity);
+        }
+        Ok(())
+    }
+
+    pub fn push_back(&mut self, element: T) {
+        if self.len == self.capacity() {
+            self.reserve(1);
+        }
+        let idx = self.to_physical(self.len);
+        unsafe { ptr::write(self.buf.ptr().add(idx), element) };
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, element: T) {
+        if self.len == self.capacity() {
+            self.reserve(1);
+        }
+        // 領域の先頭より前は、終端に折り返す
+        self.head = if self.head == 0 {
+            self.capacity() - 1
+        } else {
+            self.head - 1
+        };
+        unsafe { ptr::write(self.buf.ptr().add(self.head), element) };
+        self.len += 1;
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            let idx = self.to_physical(self.len);
+            Some(unsafe { ptr::read(self.buf.ptr().add(idx)) })
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            let elem = unsafe { ptr::read(self.buf.ptr().add(self.head)) };
+            self.head = self.to_physical(1);
+            self.len -= 1;
+            Some(elem)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        // ドロップ中にパニックしても二重にドロップしないよう、先に長さを0にする
+        self.head = 0;
+        self.len = 0;
+        unsafe { drop_slices(front, back) };
+    }
+
+    // 先頭からindex番目の要素
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(unsafe { &*self.buf.ptr().add(self.to_physical(index)) })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { &mut *self.buf.ptr().add(self.to_physical(index)) })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_or<'a>(&'a self, index: usize, default: &'a T) -> &'a T {
+        self.get(index).unwrap_or(default)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    // 要素を先頭から順に2つのスライスに分けて返す
+    // 折り返していなければ、2つ目のスライスは空になる
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.slice_ranges();
+        let ptr = self.buf.ptr();
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(front.0), front.1),
+                slice::from_raw_parts(ptr.add(back.0), back.1),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.slice_ranges();
+        let ptr = self.buf.ptr();
+        // 2つの範囲は重ならないので、同時に可変の参照を作ってよい
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(front.0), front.1),
+                slice::from_raw_parts_mut(ptr.add(back.0), back.1),
+            )
+        }
+    }
+
+    // 折り返している要素を並べ替え、全要素を1つのスライスとして返す
+    // 領域の確保はしない
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let cap = self.capacity();
+        if self.head > cap - self.len {
+            let ptr = self.buf.ptr();
+            let head_len = cap - self.head;
+            let tail_len = self.len - head_len;
+            let start = cap - self.len;
+            unsafe {
+                // 領域の先頭に折り返した部分を、先頭の部分の直前に寄せる
+                ptr::copy(ptr, ptr.add(start), tail_len);
+                // [折り返した部分, 先頭の部分]の順に並んだので、回転して入れ替える
+                slice::from_raw_parts_mut(ptr.add(start), self.len).rotate_left(tail_len);
+            }
+            self.head = start;
+        }
+        self.as_mut_slices().0
+    }
+
+    pub fn iter(&self) -> DequeIter<'_, T> {
+        let (front, back) = self.as_slices();
+        DequeIter {
+            front: Iter { elements: front },
+            back: Iter { elements: back },
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> DequeIterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        DequeIterMut {
+            front: IterMut { elements: front },
+            back: IterMut { elements: back },
+        }
+    }
+
+    // 先頭からidx番目の要素がある位置
+    // head + idxは溢れうるので、足す前に終端までの残りと比べる
+    fn to_physical(&self, idx: usize) -> usize {
+        let room = self.capacity() - self.head;
+        if idx >= room {
+            idx - room
+        } else {
+            self.head + idx
+        }
+    }
+
+    // as_slicesで返す2つのスライスの(開始位置, 長さ)
+    fn slice_ranges(&self) -> ((usize, usize), (usize, usize)) {
+        let room = self.capacity() - self.head;
+        if self.len <= room {
+            ((self.head, self.len), (0, 0))
+        } else {
+            ((self.head, room), (0, self.len - room))
+        }
+    }
+
+    // 容量がold_capacityから広がった直後に呼ぶ
+    // 折り返していた要素を、広がった領域の中で再び環状に並ぶよう移す
+    fn handle_capacity_increase(&mut self, old_capacity: usize) {
+        if self.head <= old_capacity - self.len {
+            // 折り返していなければそのままでよい
+            return;
+        }
+        let new_capacity = self.capacity();
+        let head_len = old_capacity - self.head;
+        let tail_len = self.len - head_len;
+        let ptr = self.buf.ptr();
+        unsafe {
+            if tail_len < head_len && tail_len <= new_capacity - old_capacity {
+                // 折り返した部分のほうが短ければ、旧領域の終端の後ろへ移す
+                ptr::copy_nonoverlapping(ptr, ptr.add(old_capacity), tail_len);
+            } else {
+                // そうでなければ先頭の部分を新しい領域の終端に寄せる
+                let new_head = new_capacity - head_len;
+                ptr::copy(ptr.add(self.head), ptr.add(new_head), head_len);
+                self.head = new_head;
+            }
+        }
+    }
+
+    // Dropを走らせずに、領域と先頭の位置、長さを取り出す
+    fn into_raw_buf(self) -> (RawToyVec<T, A>, usize, usize) {
+        let me = ManuallyDrop::new(self);
+        let buf = unsafe { ptr::read(&me.buf) };
+        (buf, me.head, me.len)
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> Drop for ToyDeque<T, A, G> {
+    // 領域はこの後bufのドロップで解放される
+    // DequeIntoIterのドロップでも、残った要素はここでドロップされる
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        unsafe { drop_slices(front, back) };
+    }
+}
+
+// 折り返しの前後の2つのスライスの要素を、前から順にドロップする
+// frontの要素のデストラクタがパニックしても、backの要素はガードのドロップでドロップされる
+unsafe fn drop_slices<T>(front: *mut [T], back: *mut [T]) {
+    struct Dropper<T>(*mut [T]);
+
+    impl<T> Drop for Dropper<T> {
+        fn drop(&mut self) {
+            unsafe { ptr::drop_in_place(self.0) }
+        }
+    }
+
+    let _back = Dropper(back);
+    ptr::drop_in_place(front);
+}
+
+impl<T, G: GrowthPolicy> Default for ToyDeque<T, Global, G> {
+    fn default() -> Self {
+        Self::from_buf(RawToyVec::new_in(Global), 0, 0)
+    }
+}
+
+// クローンでは要素を折り返さずに並べる
+impl<T: Clone, A: Allocator + Clone, G: GrowthPolicy> Clone for ToyDeque<T, A, G> {
+    fn clone(&self) -> Self {
+        let buf = RawToyVec::with_capacity_in(self.len, self.buf.allocator().clone());
+        let mut deque = Self::from_buf(buf, 0, 0);
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T, G: GrowthPolicy> FromIterator<T> for ToyDeque<T, Global, G> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::default();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> Extend<T> for ToyDeque<T, A, G> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator, G: GrowthPolicy> fmt::Debug for ToyDeque<T, A, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// ToyVecの領域をそのまま引き継ぐので、要素のコピーも確保も発生しない
+impl<T, A: Allocator, G: GrowthPolicy> From<ToyVec<T, A, G>> for ToyDeque<T, A, G> {
+    fn from(v: ToyVec<T, A, G>) -> Self {
+        let (buf, len) = v.into_raw_buf();
+        Self::from_buf(buf, 0, len)
+    }
+}
+
+// 要素を領域の先頭に詰めてから、領域ごとToyVecに引き渡す
+// 既に先頭から並んでいれば、要素のコピーも確保も発生しない
+impl<T, A: Allocator, G: GrowthPolicy> From<ToyDeque<T, A, G>> for ToyVec<T, A, G> {
+    fn from(mut deque: ToyDeque<T, A, G>) -> Self {
+        deque.make_contiguous();
+        let (buf, head, len) = deque.into_raw_buf();
+        if head != 0 {
+            unsafe { ptr::copy(buf.ptr().add(head), buf.ptr(), len) };
+        }
+        ToyVec::from_buf(buf, len)
+    }
+}
+
+// ToyDequeの要素への参照を先頭から返すイテレータ
+// 折り返しの前後の2つのスライスを順に辿る
+pub struct DequeIter<'a, T> {
+    front: Iter<'a, T>,
+    back: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for DequeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DequeIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DequeIter<'a, T> {}
+
+impl<'a, T> FusedIterator for DequeIter<'a, T> {}
+
+impl<'a, T> Clone for DequeIter<'a, T> {
+    fn clone(&self) -> Self {
+        DequeIter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+pub struct DequeIterMut<'a, T> {
+    front: IterMut<'a, T>,
+    back: IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for DequeIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.front.next() {
+            Some(elem) => Some(elem),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DequeIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        match self.back.next_back() {
+            Some(elem) => Some(elem),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DequeIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for DequeIterMut<'a, T> {}
+
+// ToyDequeを消費して要素をムーブしながら返すイテレータ
+// 両端から取り出すだけなので、ToyDequeのpop_frontとpop_backをそのまま使う
+pub struct DequeIntoIter<T, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    deque: ToyDeque<T, A, G>,
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> Iterator for DequeIntoIter<T, A, G> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for DequeIntoIter<T, A, G> {
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> ExactSizeIterator for DequeIntoIter<T, A, G> {}
+
+impl<T, A: Allocator, G: GrowthPolicy> FusedIterator for DequeIntoIter<T, A, G> {}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> IntoIterator for &'a ToyDeque<T, A, G> {
+    type Item = &'a T;
+    type IntoIter = DequeIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> IntoIterator for &'a mut ToyDeque<T, A, G> {
+    type Item = &'a mut T;
+    type IntoIter = DequeIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, A: Allocator, G: GrowthPolicy> IntoIterator for ToyDeque<T, A, G> {
+    type Item = T;
+    type IntoIter = DequeIntoIter<T, A, G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DequeIntoIter { deque: self }
+    }
+}
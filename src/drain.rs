@@ -0,0 +1,149 @@
+use core::iter::FusedIterator;
+use core::{ptr, slice};
+
+use crate::allocator::{Allocator, Global};
+use crate::growth::{Doubling, GrowthPolicy};
+use crate::raw::handle_reserve;
+use crate::ToyVec;
+
+// ToyVecの指定範囲の要素をムーブしながら返すイテレータ
+// ドロップされると、返さなかった要素をドロップし、範囲の後ろの要素を前に詰める
+//
+// 作成時にToyVecの長さを範囲の先頭まで縮めておくので、Drainがmem::forgetされても
+// ToyVecは範囲より前の要素だけを持つ有効な状態のまま残る（範囲と後ろの要素はリークする）
+pub struct Drain<'a, T, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    vec: &'a mut ToyVec<T, A, G>,
+    // 取り除く範囲のうち、まだ返していない要素はidx..endにある
+    idx: usize,
+    end: usize,
+    // 範囲の後ろに残す要素の位置と個数
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> Drain<'a, T, A, G> {
+    // vecの長さはすでにrangeの先頭まで縮められていること
+    pub(crate) fn new(vec: &'a mut ToyVec<T, A, G>, start: usize, end: usize, len: usize) -> Self {
+        debug_assert_eq!(vec.len, start);
+        Self {
+            vec,
+            idx: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+        }
+    }
+
+    // 取り除いた範囲の跡（vec.len..tail_start）をreplace_withの要素で先頭から埋める
+    // 跡がすべて埋まったらtrueを、replace_withが先に尽きたらfalseを返す
+    pub(crate) fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
+        let vec = &mut *self.vec;
+        while vec.len < self.tail_start {
+            match replace_with.next() {
+                Some(elem) => {
+                    unsafe { ptr::write(vec.buf.ptr().add(vec.len), elem) };
+                    vec.len += 1;
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+
+    // 範囲の後ろの要素をadditional個分さらに後ろへずらし、埋める場所を空ける
+    pub(crate) fn move_tail(&mut self, additional: usize) {
+        let vec = &mut *self.vec;
+        let used = self.tail_start + self.tail_len;
+        handle_reserve(vec.buf.try_reserve::<G>(used, additional));
+        let new_tail_start = self.tail_start + additional;
+        unsafe {
+            let base = vec.buf.ptr();
+            ptr::copy(
+                base.add(self.tail_start),
+                base.add(new_tail_start),
+                self.tail_len,
+            );
+        }
+        self.tail_start = new_tail_start;
+    }
+
+    // 範囲の後ろに要素が残っていなければtrue
+    pub(crate) fn tail_is_empty(&self) -> bool {
+        self.tail_len == 0
+    }
+
+    pub(crate) fn vec_mut(&mut self) -> &mut ToyVec<T, A, G> {
+        self.vec
+    }
+
+    // まだ返していない要素をスライスとして返す
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.vec.buf.ptr().add(self.idx), self.end - self.idx) }
+    }
+}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> Iterator for Drain<'a, T, A, G> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.idx >= self.end {
+            None
+        } else {
+            let elem = unsafe { ptr::read(self.vec.buf.ptr().add(self.idx)) };
+            self.idx += 1;
+            Some(elem)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for Drain<'a, T, A, G> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.idx >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(unsafe { ptr::read(self.vec.buf.ptr().add(self.end)) })
+        }
+    }
+}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> ExactSizeIterator for Drain<'a, T, A, G> {}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> FusedIterator for Drain<'a, T, A, G> {}
+
+impl<'a, T, A: Allocator, G: GrowthPolicy> Drop for Drain<'a, T, A, G> {
+    fn drop(&mut self) {
+        // 後ろの要素を前に詰めてToyVecの長さを戻すガード
+        // 残りの要素のドロップ中にパニックしても、このガードのドロップで詰められる
+        struct MoveTail<'r, 'a, T, A: Allocator, G: GrowthPolicy>(&'r mut Drain<'a, T, A, G>);
+
+        impl<'r, 'a, T, A: Allocator, G: GrowthPolicy> Drop for MoveTail<'r, 'a, T, A, G> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let vec = &mut *drain.vec;
+                let start = vec.len;
+                if drain.tail_len > 0 && drain.tail_start != start {
+                    unsafe {
+                        let base = vec.buf.ptr();
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                }
+                vec.len = start + drain.tail_len;
+            }
+        }
+
+        let remaining = ptr::slice_from_raw_parts_mut(
+            unsafe { self.vec.buf.ptr().add(self.idx) },
+            self.end - self.idx,
+        );
+        self.idx = self.end;
+        let guard = MoveTail(self);
+        unsafe { ptr::drop_in_place(remaining) };
+        drop(guard);
+    }
+}
@@ -0,0 +1,70 @@
+use core::alloc::Layout;
+use core::fmt;
+
+// try_reserveなどの失敗を表すエラー
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+// 失敗の原因
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TryReserveErrorKind {
+    // 要求された容量がusizeやisize::MAXバイトを超える
+    CapacityOverflow,
+    // アロケータが領域を確保できなかった。layoutは要求した領域のレイアウト
+    AllocError { layout: Layout },
+}
+
+impl TryReserveError {
+    pub fn kind(&self) -> TryReserveErrorKind {
+        self.kind.clone()
+    }
+}
+
+impl From<TryReserveErrorKind> for TryReserveError {
+    fn from(kind: TryReserveErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")?;
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                f.write_str(" because the computed capacity exceeded the collection's maximum")
+            }
+            TryReserveErrorKind::AllocError { layout } => write!(
+                f,
+                " because the memory allocator returned an error (size: {}, align: {})",
+                layout.size(),
+                layout.align()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryReserveError {}
+
+// ArrayToyVecが満杯で要素を追加できなかったことを表すエラー
+// 追加しようとした値をそのまま持つので、呼び出し側で取り戻せる
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    // 追加できなかった値を取り出す
+    pub fn element(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
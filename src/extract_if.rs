@@ -0,0 +1,89 @@
+use core::{ptr, slice};
+
+use crate::allocator::{Allocator, Global};
+use crate::growth::{Doubling, GrowthPolicy};
+use crate::ToyVec;
+
+// ToyVecの指定範囲のうち、述語がtrueを返した要素を取り除いて返すイテレータ
+// 要素は必要になるまで調べない。ドロップされると、調べていない要素はそのまま残される
+//
+// 作成時にToyVecの長さを0にしておくので、ExtractIfがmem::forgetされると
+// 全要素がリークするが、ToyVecは空の有効な状態のまま残る
+pub struct ExtractIf<'a, T, F, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    vec: &'a mut ToyVec<T, A, G>,
+    // 次に調べる要素のインデックス
+    idx: usize,
+    // 調べる範囲の終端
+    end: usize,
+    // これまでに取り除いた要素の数。残す要素はこの数だけ前に詰める
+    del: usize,
+    // 作成時のToyVecの長さ
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, F, A: Allocator, G: GrowthPolicy> ExtractIf<'a, T, F, A, G> {
+    pub(crate) fn new(vec: &'a mut ToyVec<T, A, G>, start: usize, end: usize, pred: F) -> Self {
+        let old_len = vec.len;
+        vec.len = 0;
+        Self {
+            vec,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            pred,
+        }
+    }
+}
+
+impl<'a, T, F, A, G> Iterator for ExtractIf<'a, T, F, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+    A: Allocator,
+    G: GrowthPolicy,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            let v = slice::from_raw_parts_mut(self.vec.buf.ptr(), self.old_len);
+            while self.idx < self.end {
+                let i = self.idx;
+                let extracted = (self.pred)(&mut v[i]);
+                // 述語がパニックしたときはこの要素を未処理のまま残すため、呼び出しの後に進める
+                self.idx += 1;
+                if extracted {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - self.del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<'a, T, F, A: Allocator, G: GrowthPolicy> Drop for ExtractIf<'a, T, F, A, G> {
+    // 調べていない要素と範囲の後ろの要素を前に詰め、ToyVecの長さを戻す
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let base = self.vec.buf.ptr();
+                ptr::copy(
+                    base.add(self.idx),
+                    base.add(self.idx - self.del),
+                    self.old_len - self.idx,
+                );
+            }
+            self.vec.len = self.old_len - self.del;
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// push、reserve、extendで領域が足りなくなったときに、新しい容量を決める方針
+//
+// ToyVecの3番目の型パラメータで選ぶ。省略するとDoublingを使う
+// サイズ0の型の要素では容量が常にusize::MAXなので、呼ばれることはない
+pub trait GrowthPolicy {
+    // 容量currentの領域に少なくともrequired個（> current）の要素を格納したいときの新しい容量を返す
+    // elem_sizeは要素1つのバイト数。required未満を返しても、required個分は確保される
+    fn grow(current: usize, required: usize, elem_size: usize) -> usize;
+}
+
+// 容量を倍にする。空の状態からは必要な数ちょうどを確保する
+// 伸長の回数が少なく償却O(1)だが、最大で半分近くの領域が無駄になる
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+    fn grow(current: usize, required: usize, _elem_size: usize) -> usize {
+        required.max(current.saturating_mul(2))
+    }
+}
+
+// 容量を1.5倍にする。Doublingより無駄な領域が少ないが、伸長の回数は増える
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OneAndHalf;
+
+impl GrowthPolicy for OneAndHalf {
+    fn grow(current: usize, required: usize, _elem_size: usize) -> usize {
+        required.max(current.saturating_add(current / 2))
+    }
+}
+
+// 容量をN個ずつ増やす。無駄な領域はN個未満に収まるが、pushは償却O(1)にならない
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixedIncrement<const N: usize>;
+
+impl<const N: usize> GrowthPolicy for FixedIncrement<N> {
+    fn grow(current: usize, required: usize, _elem_size: usize) -> usize {
+        required.max(current.saturating_add(N))
+    }
+}
+
+// Doublingで決めた容量のバイト数を、アロケータが実際に割り当てる大きさに切り上げる
+// ページサイズ未満なら2のべき乗に、それ以上ならページサイズの倍数にする
+// 切り上げで生じる隙間も要素の格納に使えるので、伸長の回数を減らせる
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SizeClass;
+
+impl SizeClass {
+    pub const PAGE_SIZE: usize = 4096;
+}
+
+impl GrowthPolicy for SizeClass {
+    fn grow(current: usize, required: usize, elem_size: usize) -> usize {
+        let capacity = Doubling::grow(current, required, elem_size);
+        // サイズ0の型は丸めるバイト数がないので、Doublingと同じ容量にする
+        if elem_size == 0 {
+            return capacity;
+        }
+        let bytes = match capacity.checked_mul(elem_size) {
+            Some(bytes) => bytes,
+            // 溢れる容量はどうせ確保できないので、そのまま返してエラーにさせる
+            None => return capacity,
+        };
+        let rounded = if bytes < Self::PAGE_SIZE {
+            bytes.next_power_of_two()
+        } else {
+            match bytes.checked_add(Self::PAGE_SIZE - 1) {
+                Some(b) => b / Self::PAGE_SIZE * Self::PAGE_SIZE,
+                None => bytes,
+            }
+        };
+        rounded / elem_size
+    }
+}
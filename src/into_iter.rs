@@ -0,0 +1,116 @@
+use core::iter::FusedIterator;
+use core::{ptr, slice};
+
+use crate::allocator::{Allocator, Global};
+use crate::raw::RawToyVec;
+use crate::ToyVec;
+
+// ToyVecを消費して要素をムーブしながら返すイテレータ
+// ToyVecの領域をそのまま引き継ぎ、start..endの範囲の要素だけが初期化済み
+pub struct IntoIter<T, A: Allocator = Global> {
+    buf: RawToyVec<T, A>,
+    // 次にnextで返す要素のインデックス
+    start: usize,
+    // まだ返していない要素の終端
+    end: usize,
+}
+
+impl<T, A: Allocator> IntoIter<T, A> {
+    pub(crate) fn new(buf: RawToyVec<T, A>, len: usize) -> Self {
+        Self {
+            buf,
+            start: 0,
+            end: len,
+        }
+    }
+
+    // まだ返していない要素をスライスとして返す
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.ptr().add(self.start), self.end - self.start) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.ptr().add(self.start), self.end - self.start) }
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start >= self.end {
+            None
+        } else {
+            // 読み出した要素は未初期化の扱いになり、dropでは二重にドロップされない
+            let elem = unsafe { ptr::read(self.buf.ptr().add(self.start)) };
+            self.start += 1;
+            Some(elem)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+
+    // 読み飛ばすn個の要素はまとめてドロップする
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let skip = n.min(self.end - self.start);
+        let skipped =
+            ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr().add(self.start) }, skip);
+        // 先にstartを進めておき、ドロップ中にパニックしても二重にドロップしないようにする
+        self.start += skip;
+        unsafe { ptr::drop_in_place(skipped) };
+        self.next()
+    }
+
+    // 残りの要素はselfのドロップでまとめてドロップされる
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<T> {
+        self.next_back()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(unsafe { ptr::read(self.buf.ptr().add(self.end)) })
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<T> {
+        let skip = n.min(self.end - self.start);
+        self.end -= skip;
+        let skipped = ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr().add(self.end) }, skip);
+        unsafe { ptr::drop_in_place(skipped) };
+        self.next_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+// 残りの要素をクローンした新しい領域を持つイテレータを作る
+impl<T: Clone, A: Allocator + Clone> Clone for IntoIter<T, A> {
+    fn clone(&self) -> Self {
+        let mut v = ToyVec::with_capacity_in(self.len(), self.buf.allocator().clone());
+        for elem in self.as_slice() {
+            v.push(elem.clone());
+        }
+        v.into_iter()
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    // 消費されなかった要素をドロップする。領域はこの後bufのドロップで解放される
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
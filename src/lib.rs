@@ -1,98 +1,897 @@
-pub struct ToyVec<T> {
-    elements: Box<[T]>,
+// allocクレートだけで動くので、組み込みやWASMなどstdのない環境でも使える
+// stdフィーチャ（デフォルトで有効）では、std::error::Errorの実装などstdに依存する機能を追加する
+// allocフィーチャ（stdで有効になる）を外すと、ヒープを使わないArrayToyVecだけが使える
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::iter::FusedIterator;
+use core::mem;
+#[cfg(feature = "alloc")]
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{self, FromIterator},
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr,
+    slice::{self, SliceIndex},
+};
+
+#[cfg(feature = "alloc")]
+mod allocator;
+mod array;
+#[cfg(feature = "alloc")]
+mod cmp;
+#[cfg(feature = "alloc")]
+mod convert;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "alloc")]
+mod drain;
+mod error;
+#[cfg(feature = "alloc")]
+mod extract_if;
+#[cfg(feature = "alloc")]
+mod growth;
+#[cfg(feature = "alloc")]
+mod into_iter;
+#[cfg(feature = "alloc")]
+mod macros;
+#[cfg(feature = "alloc")]
+mod raw;
+#[cfg(feature = "alloc")]
+mod small;
+#[cfg(feature = "alloc")]
+mod splice;
+
+#[cfg(feature = "alloc")]
+pub use allocator::{AllocError, Allocator, Global};
+pub use array::ArrayToyVec;
+#[cfg(feature = "alloc")]
+pub use deque::{DequeIntoIter, DequeIter, DequeIterMut, ToyDeque};
+#[cfg(feature = "alloc")]
+pub use drain::Drain;
+pub use error::{CapacityError, TryReserveError, TryReserveErrorKind};
+#[cfg(feature = "alloc")]
+pub use extract_if::ExtractIf;
+#[cfg(feature = "alloc")]
+pub use growth::{Doubling, FixedIncrement, GrowthPolicy, OneAndHalf, SizeClass};
+#[cfg(feature = "alloc")]
+pub use into_iter::IntoIter;
+#[cfg(feature = "alloc")]
+use raw::{handle_reserve, RawToyVec};
+#[cfg(feature = "alloc")]
+pub use small::SmallToyVec;
+#[cfg(feature = "alloc")]
+pub use splice::Splice;
+
+// Aは要素の領域を確保するアロケータ。省略するとグローバルアロケータを使う
+// Gは領域が足りなくなったときの伸長の方針。省略すると容量を倍にする
+#[cfg(feature = "alloc")]
+pub struct ToyVec<T, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    // 要素を格納する未初期化の領域
+    buf: RawToyVec<T, A>,
+    // 先頭からlen個の要素だけが初期化済み
     len: usize,
+    // Gは型としてだけ使う。fn() -> GにしておけばSendやSyncに影響しない
+    growth: PhantomData<fn() -> G>,
 }
 
-impl<T: Default> ToyVec<T> {
+#[cfg(feature = "alloc")]
+impl<T> ToyVec<T> {
     pub fn new() -> Self {
         Self::with_capacity(0)
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    // with_capacityと違い、領域を確保できなければパニックせずにエラーを返す
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    /// ポインタと長さ、容量からToyVecを作る
+    ///
+    /// # Safety
+    ///
+    /// `from_raw_parts_in`と同じ条件を、アロケータを`Global`として満たさなければならない
+    /// `Vec<T>`から取り出したポインタと長さ、容量も渡せる
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Self {
+        Self::from_raw_parts_in(ptr, length, capacity, Global)
+    }
+
+    // ToyVecを分解し、領域を指すポインタと長さ、容量を返す
+    // 領域の解放や要素のドロップは呼び出し側の責任になる。from_raw_partsで元に戻せる
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let (ptr, length, capacity, Global) = self.into_raw_parts_with_alloc();
+        (ptr, length, capacity)
+    }
+
+    // toy_vec![elem; n]の実装。マクロから呼ぶためだけに公開している
+    #[doc(hidden)]
+    pub fn from_elem(elem: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut v = Self::new();
+        v.reserve_exact(n);
+        v.extend_with(n, elem);
+        v
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator> ToyVec<T, A> {
+    // 領域をallocから確保するToyVecを作る。要素を追加するまで確保はしない
+    pub fn new_in(alloc: A) -> Self {
+        Self::from_buf(RawToyVec::new_in(alloc), 0)
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::from_buf(RawToyVec::with_capacity_in(capacity, alloc), 0)
+    }
+
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Self::from_buf(
+            RawToyVec::try_with_capacity_in(capacity, alloc)?,
+            0,
+        ))
+    }
+
+    /// ポインタと長さ、容量、アロケータからToyVecを作る。`into_raw_parts_with_alloc`の逆
+    ///
+    /// # Safety
+    ///
+    /// - `ptr`は`alloc`で`capacity`個のT型の配列のレイアウトを指定して確保した領域を指すこと。
+    ///   ただし`capacity`が0か、Tのサイズが0なら、アラインメントを満たす非nullのポインタであればよい
+    /// - `length`は`capacity`以下で、先頭の`length`個の要素が初期化済みであること
+    /// - 領域の所有権はToyVecに移るので、呼び出し側はこの領域をもう使わないこと
+    pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize, alloc: A) -> Self {
+        debug_assert!(length <= capacity);
+        Self::from_buf(RawToyVec::from_raw_parts_in(ptr, capacity, alloc), length)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> ToyVec<T, A, G> {
+    // 先頭len個の要素が初期化済みの領域から作る
+    fn from_buf(buf: RawToyVec<T, A>, len: usize) -> Self {
         Self {
-            elements: Self::allocate_in_heap(capacity),
-            len: 0,
+            buf,
+            len,
+            growth: PhantomData,
         }
     }
 
-    fn allocate_in_heap(size: usize) -> Box<[T]> {
-        std::iter::repeat_with(Default::default)
-            .take(size)
-            .collect::<Vec<_>>()
-            .into_boxed_slice()
+    // 要素と領域はそのままで、伸長の方針だけを変えたToyVecにする
+    // 例えば ToyVec::with_capacity(8).with_growth_policy::<OneAndHalf>() のように使う
+    pub fn with_growth_policy<H: GrowthPolicy>(self) -> ToyVec<T, A, H> {
+        let (buf, len) = self.into_raw_buf();
+        ToyVec::from_buf(buf, len)
+    }
+
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    // into_raw_partsと同じだが、アロケータも返す
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        let (buf, length) = self.into_raw_buf();
+        let buf = ManuallyDrop::new(buf);
+        let alloc = unsafe { ptr::read(buf.allocator()) };
+        (buf.ptr(), length, buf.capacity(), alloc)
+    }
+
+    // 領域の先頭を指すポインタを返す。要素がなくてもnullにはならない
+    // スライスのas_ptrと違い、容量いっぱいまでの領域を指すポインタとして使える
+    pub fn as_ptr(&self) -> *const T {
+        self.buf.ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.ptr()
+    }
+
+    /// 長さを`new_len`に変える。要素の初期化やドロップは行わない
+    ///
+    /// # Safety
+    ///
+    /// - `new_len`は`capacity()`以下であること
+    /// - `old_len..new_len`の要素は初期化済みであること
+    /// - 長さを縮める場合、`new_len..old_len`の要素のドロップは呼び出し側で行うこと
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.len = new_len;
+    }
+
+    // 長さから容量までの未初期化の領域をMaybeUninitのスライスとして返す
+    // 書き込んだ後にset_lenで長さを伸ばせば、それらが要素になる
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.buf.ptr().add(self.len) as *mut MaybeUninit<T>,
+                self.capacity() - self.len,
+            )
+        }
     }
 
     pub fn len(&self) -> usize {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn capacity(&self) -> usize {
-        self.elements.len()
+        self.buf.capacity()
+    }
+
+    // 少なくともadditional個の要素を追加で格納できるよう容量を確保する
+    // 新しい容量はpushと同じくGの方針で決める。GrowthPolicyによっては必要な数より多く確保する
+    pub fn reserve(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve(additional));
+    }
+
+    // reserveと違い、ちょうどadditional個分だけ容量を広げる
+    pub fn reserve_exact(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve_exact(additional));
+    }
+
+    // reserveと違い、容量が溢れたり確保に失敗したりしたらエラーを返す
+    // エラーのときToyVecは変更されない
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve::<G>(self.len, additional)
+    }
+
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve_exact(self.len, additional)
+    }
+
+    // 容量をできるだけlenに近づけ、余分な領域を返却する
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    // 容量をmin_capacityまで縮める。ただしlenより小さくはしない
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let new_capacity = self.len.max(min_capacity);
+        if new_capacity < self.capacity() {
+            self.buf.shrink_to(new_capacity);
+        }
     }
 
     pub fn push(&mut self, element: T) {
         if self.len == self.capacity() {
-            self.grow();
+            handle_reserve(self.grow());
         }
-        self.elements[self.len] = element;
+        // 未初期化の領域に書き込むので、古い値をドロップしないptr::writeを使う
+        unsafe { ptr::write(self.buf.ptr().add(self.len), element) };
         self.len += 1;
     }
 
+    // pushと違い、領域を広げられなければパニックせずにエラーを返す
+    // エラーのときelementはドロップされ、ToyVecは変更されない
+    pub fn try_push(&mut self, element: T) -> Result<(), TryReserveError> {
+        if self.len == self.capacity() {
+            self.grow()?;
+        }
+        unsafe { ptr::write(self.buf.ptr().add(self.len), element) };
+        self.len += 1;
+        Ok(())
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             None
         } else {
             self.len -= 1;
-            let elem = std::mem::replace(&mut self.elements[self.len], Default::default());
+            // 値を読み出したスロットは未初期化として扱い、代わりの値は書き込まない
+            let elem = unsafe { ptr::read(self.buf.ptr().add(self.len)) };
             Some(elem)
         }
     }
 
+    // index番目に要素を挿入し、それ以降の要素を後ろにずらす
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len;
+        if index > len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+        if len == self.capacity() {
+            handle_reserve(self.grow());
+        }
+        unsafe {
+            let p = self.buf.ptr().add(index);
+            // index以降の要素をまとめて1つ後ろへ移動する。領域が重なるのでcopyを使う
+            ptr::copy(p, p.add(1), len - index);
+            ptr::write(p, element);
+        }
+        self.len = len + 1;
+    }
+
+    // index番目の要素を取り除いて返し、それ以降の要素を前に詰める
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        if index >= len {
+            panic!("removal index (is {}) should be < len (is {})", index, len);
+        }
+        unsafe {
+            let p = self.buf.ptr().add(index);
+            let elem = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.len = len - 1;
+            elem
+        }
+    }
+
+    // index番目の要素を取り除いて返し、空いた場所に最後の要素を移す
+    // 要素の順序は保たれないが、O(1)で済む
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len;
+        if index >= len {
+            panic!(
+                "swap_remove index (is {}) should be < len (is {})",
+                index, len
+            );
+        }
+        unsafe {
+            let base = self.buf.ptr();
+            let elem = ptr::read(base.add(index));
+            // index == len - 1のときは同じ場所へのコピーになるが問題ない
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.len = len - 1;
+            elem
+        }
+    }
+
+    // 先頭のlen個だけを残し、残りの要素をドロップする。容量は変わらない
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail =
+            ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr().add(len) }, self.len - len);
+        // 先にlenを更新しておき、ドロップ中にパニックしても二重にドロップしないようにする
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    // すべての要素をドロップする。容量は変わらない
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // range内の要素を取り除き、それらをムーブしながら返すイテレータを作る
+    // イテレータがドロップされると、rangeの後ろの要素が前に詰められる
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A, G> {
+        let len = self.len;
+        let Range { start, end } = slice_range(range, len);
+        // Drainが後始末をする前にリークされても取り出し中の要素に触れないよう、
+        // 長さを範囲の先頭まで縮めておく
+        self.len = start;
+        Drain::new(self, start, end, len)
+    }
+
+    // rangeの要素をreplace_withの要素で置き換え、取り除いた要素を返すイテレータを作る
+    // 置き換えはイテレータがドロップされたときに行われる
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, A, G>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice::new(self.drain(range), replace_with.into_iter())
+    }
+
+    // range内の要素のうち、predがtrueを返したものを取り除いて返すイテレータを作る
+    // retainと違い、イテレータを進めた分だけ要素を調べる
+    pub fn extract_if<F, R>(&mut self, range: R, pred: F) -> ExtractIf<'_, T, F, A, G>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = slice_range(range, self.len);
+        ExtractIf::new(self, start, end, pred)
+    }
+
+    // fがtrueを返した要素だけを、順序を保って残す
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    // retainと同じだが、fは要素への可変の参照を受け取る
+    // 各要素を一度だけ調べ、残す要素を前に詰めながら1パスで処理する
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        // fやデストラクタがパニックしたときに、未処理の要素を前に詰めて長さを戻すガード
+        struct Guard<'a, T, A: Allocator, G: GrowthPolicy> {
+            vec: &'a mut ToyVec<T, A, G>,
+            // 調べ終えた要素の数と、そのうち取り除いた要素の数
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+
+        impl<'a, T, A: Allocator, G: GrowthPolicy> Drop for Guard<'a, T, A, G> {
+            fn drop(&mut self) {
+                if self.deleted > 0 {
+                    unsafe {
+                        let base = self.vec.buf.ptr();
+                        ptr::copy(
+                            base.add(self.processed),
+                            base.add(self.processed - self.deleted),
+                            self.original_len - self.processed,
+                        );
+                    }
+                }
+                self.vec.len = self.original_len - self.deleted;
+            }
+        }
+
+        let original_len = self.len;
+        // 処理中は要素の並びに穴が空くので、パニックしてもその状態が見えないよう長さを0にしておく
+        self.len = 0;
+        let mut g = Guard {
+            vec: self,
+            processed: 0,
+            deleted: 0,
+            original_len,
+        };
+        while g.processed < original_len {
+            let cur = unsafe { &mut *g.vec.buf.ptr().add(g.processed) };
+            if f(cur) {
+                if g.deleted > 0 {
+                    unsafe {
+                        let hole = g.vec.buf.ptr().add(g.processed - g.deleted);
+                        ptr::copy_nonoverlapping(cur, hole, 1);
+                    }
+                }
+                g.processed += 1;
+            } else {
+                // ドロップの前に数を進め、デストラクタがパニックしても二重にドロップしないようにする
+                g.processed += 1;
+                g.deleted += 1;
+                unsafe { ptr::drop_in_place(cur) };
+            }
+        }
+    }
+
+    // 連続する等しい要素を1つにまとめる
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    // 連続する要素のうち、keyが同じ値を返すものを1つにまとめる
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // 連続する要素のうち、same_bucketがtrueを返すものを1つにまとめる
+    // same_bucketには後ろの要素と、残すことが決まった直前の要素がこの順で渡される
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        // same_bucketやデストラクタがパニックしたときに、未処理の要素を前に詰めて長さを戻すガード
+        struct FillGap<'a, T, A: Allocator, G: GrowthPolicy> {
+            vec: &'a mut ToyVec<T, A, G>,
+            // 次に調べる要素と、次に残す要素を書き込む位置
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<'a, T, A: Allocator, G: GrowthPolicy> Drop for FillGap<'a, T, A, G> {
+            fn drop(&mut self) {
+                let remaining = self.original_len - self.read;
+                unsafe {
+                    let base = self.vec.buf.ptr();
+                    ptr::copy(base.add(self.read), base.add(self.write), remaining);
+                }
+                self.vec.len = self.write + remaining;
+            }
+        }
+
+        let original_len = self.len;
+        if original_len <= 1 {
+            return;
+        }
+        self.len = 0;
+        // 先頭の要素は必ず残る
+        let mut g = FillGap {
+            vec: self,
+            read: 1,
+            write: 1,
+            original_len,
+        };
+        while g.read < original_len {
+            unsafe {
+                let base = g.vec.buf.ptr();
+                let read = base.add(g.read);
+                let prev = base.add(g.write - 1);
+                if same_bucket(&mut *read, &mut *prev) {
+                    g.read += 1;
+                    ptr::drop_in_place(read);
+                } else {
+                    ptr::copy(read, base.add(g.write), 1);
+                    g.read += 1;
+                    g.write += 1;
+                }
+            }
+        }
+    }
+
+    // otherの要素をすべて末尾にムーブし、otherを空にする
+    // 領域を一度だけ確保し、要素はまとめてコピーする
+    pub fn append(&mut self, other: &mut Self) {
+        let count = other.len;
+        self.reserve(count);
+        unsafe {
+            ptr::copy_nonoverlapping(other.buf.ptr(), self.buf.ptr().add(self.len), count);
+        }
+        // 要素の所有権はselfに移ったので、otherからは見えなくする
+        other.len = 0;
+        self.len += count;
+    }
+
+    // at番目以降の要素を新しいToyVecに移して返す。selfには先頭のat個が残る
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        if at > self.len {
+            panic!(
+                "`at` split index (is {}) should be <= len (is {})",
+                at, self.len
+            );
+        }
+        let count = self.len - at;
+        let buf = RawToyVec::with_capacity_in(count, self.allocator().clone());
+        let mut other = Self::from_buf(buf, 0);
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.ptr().add(at), other.buf.ptr(), count);
+        }
+        self.len = at;
+        other.len = count;
+        other
+    }
+
+    // 長さをnew_lenに変える。伸ばすときはvalueのクローンで埋める
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        if new_len > self.len {
+            self.extend_with(new_len - self.len, value);
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    // 長さをnew_lenに変える。伸ばすときはfを呼んで得た値で埋める
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        if new_len > self.len {
+            let additional = new_len - self.len;
+            self.extend_from_iter(iter::repeat_with(f).take(additional));
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    // n個の要素をvalueのクローンで埋める。最後の1つはクローンせずにvalueをムーブする
+    fn extend_with(&mut self, n: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.reserve(n);
+        unsafe {
+            let mut p = self.buf.ptr().add(self.len);
+            // cloneがパニックしても書き込めた分は残るよう、1つずつlenを進める
+            for _ in 1..n {
+                ptr::write(p, value.clone());
+                p = p.add(1);
+                self.len += 1;
+            }
+            if n > 0 {
+                ptr::write(p, value);
+                self.len += 1;
+            }
+        }
+    }
+
+    // スライスの要素をクローンして末尾に追加する。領域は先に一度だけ確保する
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(other.len());
+        self.extend_from_iter(other.iter().cloned());
+    }
+
+    // イテレータの要素を末尾に追加する
+    // 領域が足りなくなったら、size_hintの下限の分もまとめて確保する
+    fn extend_from_iter<I: Iterator<Item = T>>(&mut self, mut iter: I) {
+        while let Some(elem) = iter.next() {
+            if self.len == self.capacity() {
+                let (lower, _) = iter.size_hint();
+                self.reserve(lower.saturating_add(1));
+            }
+            unsafe { ptr::write(self.buf.ptr().add(self.len), elem) };
+            self.len += 1;
+        }
+    }
+
+    // 初期化済みの要素をスライスとして返す
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.ptr(), self.len) }
+    }
+
     pub fn get(&self, index: usize) -> Option<&T> {
         if index < self.len {
-            Some(&self.elements[index])
+            Some(unsafe { &*self.buf.ptr().add(index) })
         } else {
             None
         }
     }
 
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            Some(unsafe { &mut *self.buf.ptr().add(index) })
+        } else {
+            None
+        }
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.len.checked_sub(1).and_then(move |i| self.get_mut(i))
+    }
+
+    // a番目とb番目の要素を入れ替える。どちらかが範囲外ならパニックする
+    pub fn swap(&mut self, a: usize, b: usize) {
+        assert!(
+            a < self.len,
+            "swap index (is {}) should be < len (is {})",
+            a,
+            self.len
+        );
+        assert!(
+            b < self.len,
+            "swap index (is {}) should be < len (is {})",
+            b,
+            self.len
+        );
+        self.as_mut_slice().swap(a, b);
+    }
+
     pub fn get_or<'a>(&'a self, index: usize, default: &'a T) -> &'a T {
         self.get(index).unwrap_or(default)
     }
 
-    fn grow(&mut self) {
-        if self.capacity() == 0 {
-            self.elements = Self::allocate_in_heap(1);
-        } else {
-            let new_elements = Self::allocate_in_heap(self.capacity() * 2);
-            let old_elements = std::mem::replace(&mut self.elements, new_elements);
-
-            for (i, elem) in old_elements.into_vec().into_iter().enumerate() {
-                self.elements[i] = elem;
-            }
-        }
+    // 1つ以上の要素を追加できるよう、Gの方針で容量を広げる
+    fn grow(&mut self) -> Result<(), TryReserveError> {
+        // 要素は領域ごと移動するので、ひとつずつ移し替える必要はない
+        self.buf.try_reserve::<G>(self.len, 1)
     }
 
     // 説明のためにライフタイムを明示しているが、本当は省略できる
     pub fn iter<'vec>(&'vec self) -> Iter<'vec, T> {
         Iter {
             // Iter構造体の定義より、ライフタイムは'vecになる
-            elements: &self.elements,
-            len: self.len,
-            pos: 0,
+            elements: self.as_slice(),
         }
     }
+
+    // 要素への可変の参照を返すイテレータ
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            elements: self.as_mut_slice(),
+        }
+    }
+
+    // Dropを走らせずに、領域と長さを取り出す
+    fn into_raw_buf(self) -> (RawToyVec<T, A>, usize) {
+        let me = ManuallyDrop::new(self);
+        // meはドロップされないので、bufの所有権を読み出しても二重解放にはならない
+        let buf = unsafe { ptr::read(&me.buf) };
+        (buf, me.len)
+    }
+}
+
+// RangeBoundsで指定された範囲を、長さlenのスライスに対する半開区間に変換する
+// 範囲が不正ならスライスの添字と同じようにパニックする
+#[cfg(feature = "alloc")]
+fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice from after maximum usize")),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice up to maximum usize")),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("slice index starts at {} but ends at {}", start, end);
+    }
+    if end > len {
+        panic!(
+            "range end index {} out of range for slice of length {}",
+            end, len
+        );
+    }
+    start..end
+}
+
+// スライスへの参照外しにより、sortやbinary_searchなどのスライスのメソッドがそのまま使える
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> Deref for ToyVec<T, A, G> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> DerefMut for ToyVec<T, A, G> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+// v[i]やv[1..3]のように、スライスと同じ添字で要素や部分スライスを得られる
+// 範囲外の添字はスライスと同様に、添字とlenを示してパニックする
+#[cfg(feature = "alloc")]
+impl<T, I: SliceIndex<[T]>, A: Allocator, G: GrowthPolicy> Index<I> for ToyVec<T, A, G> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, I: SliceIndex<[T]>, A: Allocator, G: GrowthPolicy> IndexMut<I> for ToyVec<T, A, G> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> Drop for ToyVec<T, A, G> {
+    // 初期化済みのlen個の要素だけを先頭から順にドロップする
+    // スライスに対するdrop_in_placeは、途中の要素のデストラクタがパニックしても
+    // 残りの要素のドロップを続ける。領域はこの後bufのドロップで解放される
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, G: GrowthPolicy> Default for ToyVec<T, Global, G> {
+    fn default() -> Self {
+        Self::from_buf(RawToyVec::new_in(Global), 0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, A: Allocator + Clone, G: GrowthPolicy> Clone for ToyVec<T, A, G> {
+    // 容量は要素数ちょうどにする
+    fn clone(&self) -> Self {
+        let mut v = Self::from_buf(RawToyVec::new_in(self.allocator().clone()), 0);
+        v.reserve_exact(self.len);
+        v.extend_from_iter(self.iter().cloned());
+        v
+    }
+
+    // 既存の要素と領域を使い回し、足りない分だけ確保する
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len);
+        let (init, tail) = source.split_at(self.len);
+        // 共通する長さの分は要素ごとのclone_fromで上書きする
+        self.clone_from_slice(init);
+        self.extend_from_slice(tail);
+    }
+}
+
+// size_hintの下限の分だけ先に領域を確保してから集める
+// ToyVecのIntoIterやIterなど、要素数が正確にわかるイテレータなら確保は1回で済む
+#[cfg(feature = "alloc")]
+impl<T, G: GrowthPolicy> FromIterator<T> for ToyVec<T, Global, G> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut v = Self::default();
+        v.extend(iter);
+        v
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> Extend<T> for ToyVec<T, A, G> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        self.extend_from_iter(iter);
+    }
+}
+
+// Copyな要素なら、参照のイテレータからも追加できる
+#[cfg(feature = "alloc")]
+impl<'a, T: Copy + 'a, A: Allocator, G: GrowthPolicy> Extend<&'a T> for ToyVec<T, A, G> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+// 初期化済みの要素だけを、スライスと同じ形式で表示する
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug, A: Allocator, G: GrowthPolicy> fmt::Debug for ToyVec<T, A, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+// 同じ要素を持つスライスと同じハッシュ値になるようにする
+#[cfg(feature = "alloc")]
+impl<T: Hash, A: Allocator, G: GrowthPolicy> Hash for ToyVec<T, A, G> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
 }
 
 // ライフタイムの指定により、このイテレータ自身またはnext()で得た&'vec T型の値が
 // 生存してる間は、ToyVecは変更できない
 pub struct Iter<'vec, T> {
-    // ToyVec構造体のelementsを指す不変の参照
-    elements: &'vec Box<[T]>,
-    // ToyVecの長さ
-    len: usize,
-    // 次に返す要素のインデックス
-    pos: usize,
+    // ToyVecの要素のうち、まだ返していない部分を指す不変のスライス
+    // 前から返すときは先頭を、後ろから返すときは末尾を切り詰めていく
+    elements: &'vec [T],
 }
 
 impl<'vec, T> Iterator for Iter<'vec, T> {
@@ -102,17 +901,73 @@ impl<'vec, T> Iterator for Iter<'vec, T> {
     // nextメソッドは次の要素を返す
     // 要素があるなら不変の参照（&T）をSomeで包んで返し、ないときはNoneを返す
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.len {
+        let (first, rest) = self.elements.split_first()?;
+        self.elements = rest;
+        Some(first)
+    }
+
+    // 残りの要素数は正確にわかるので、collectなどが領域を事前に確保できる
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.elements.len(), Some(self.elements.len()))
+    }
+
+    // 以下はデフォルト実装だとnextを繰り返し呼ぶので、スライスを直接操作して上書きする
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.elements.len() {
+            self.elements = &[];
+            None
+        } else {
+            let elem = &self.elements[n];
+            self.elements = &self.elements[n + 1..];
+            Some(elem)
+        }
+    }
+
+    fn count(self) -> usize {
+        self.elements.len()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.elements.last()
+    }
+}
+
+impl<'vec, T> DoubleEndedIterator for Iter<'vec, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (last, rest) = self.elements.split_last()?;
+        self.elements = rest;
+        Some(last)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.elements.len() {
+            self.elements = &[];
             None
         } else {
-            let res = Some(&self.elements[self.pos]);
-            self.pos += 1;
-            res
+            let end = self.elements.len() - n - 1;
+            let elem = &self.elements[end];
+            self.elements = &self.elements[..end];
+            Some(elem)
         }
     }
 }
 
-impl<'vec, T: Default> IntoIterator for &'vec ToyVec<T> {
+impl<'vec, T> ExactSizeIterator for Iter<'vec, T> {}
+
+// 一度Noneを返したら、その後もNoneを返し続ける
+impl<'vec, T> FusedIterator for Iter<'vec, T> {}
+
+// 参照しか持たないので、Tがクローンできなくてもイテレータはクローンできる
+impl<'vec, T> Clone for Iter<'vec, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            elements: self.elements,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'vec, T, A: Allocator, G: GrowthPolicy> IntoIterator for &'vec ToyVec<T, A, G> {
     // イテレータがイテレートする値の型
     type Item = &'vec T;
     // into_iterメソッドの戻り値の型
@@ -132,3 +987,91 @@ impl<'vec, T: Default> IntoIterator for &'vec ToyVec<T> {
 // for msg in &v {
 //     print(msg);
 // }
+
+// 要素への可変の参照を返すイテレータ
+// 返した参照同士が重ならないよう、まだ返していない部分だけをスライスとして持つ
+pub struct IterMut<'vec, T> {
+    elements: &'vec mut [T],
+}
+
+impl<'vec, T> Iterator for IterMut<'vec, T> {
+    type Item = &'vec mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 一度スライスを取り出してから分割し、残りを戻す
+        let elements = mem::take(&mut self.elements);
+        let (first, rest) = elements.split_first_mut()?;
+        self.elements = rest;
+        Some(first)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.elements.len(), Some(self.elements.len()))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let elements = mem::take(&mut self.elements);
+        if n >= elements.len() {
+            None
+        } else {
+            let (skipped, rest) = elements.split_at_mut(n + 1);
+            self.elements = rest;
+            skipped.last_mut()
+        }
+    }
+
+    fn count(self) -> usize {
+        self.elements.len()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.elements.last_mut()
+    }
+}
+
+impl<'vec, T> DoubleEndedIterator for IterMut<'vec, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elements = mem::take(&mut self.elements);
+        let (last, rest) = elements.split_last_mut()?;
+        self.elements = rest;
+        Some(last)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let elements = mem::take(&mut self.elements);
+        if n >= elements.len() {
+            None
+        } else {
+            let end = elements.len() - n - 1;
+            let (rest, skipped) = elements.split_at_mut(end);
+            self.elements = rest;
+            skipped.first_mut()
+        }
+    }
+}
+
+impl<'vec, T> ExactSizeIterator for IterMut<'vec, T> {}
+
+impl<'vec, T> FusedIterator for IterMut<'vec, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'vec, T, A: Allocator, G: GrowthPolicy> IntoIterator for &'vec mut ToyVec<T, A, G> {
+    type Item = &'vec mut T;
+    type IntoIter = IterMut<'vec, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// ToyVecそのものに対するIntoIteratorは、要素をムーブして返すIntoIterを返す
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, G: GrowthPolicy> IntoIterator for ToyVec<T, A, G> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (buf, len) = self.into_raw_buf();
+        IntoIter::new(buf, len)
+    }
+}
@@ -0,0 +1,17 @@
+// vec!と同じ書き方でToyVecを作るマクロ
+//
+// toy_vec![a, b, c]は要素を配列にまとめてからToyVecにムーブし、
+// toy_vec![elem; n]はelemをn - 1回クローンして最後にelem自身をムーブする
+// どちらも領域の確保はちょうどの容量で1回だけ行う
+#[macro_export]
+macro_rules! toy_vec {
+    () => {
+        $crate::ToyVec::new()
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::ToyVec::from_elem($elem, $n)
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::ToyVec::from([$($x),+])
+    };
+}
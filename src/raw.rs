@@ -0,0 +1,206 @@
+use alloc::alloc::handle_alloc_error;
+use core::alloc::Layout;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::NonNull;
+
+use crate::allocator::{Allocator, Global};
+use crate::error::{TryReserveError, TryReserveErrorKind};
+use crate::growth::GrowthPolicy;
+
+// ToyVecの要素を格納する未初期化のヒープ領域
+// 領域の確保と解放だけを受け持ち、要素の初期化やドロップはToyVec側で行う
+//
+// Tのサイズが0なら領域は一切確保せず、ダングリングポインタのまま容量をusize::MAXとして扱う
+pub(crate) struct RawToyVec<T, A: Allocator = Global> {
+    // 領域の先頭を指すポインタ。未確保のときはダングリングポインタ
+    ptr: NonNull<T>,
+    // 確保した領域に格納できる要素数
+    cap: usize,
+    // 領域の確保と解放に使うアロケータ
+    alloc: A,
+    // このバッファがT型の値を所有することをコンパイラに伝える
+    _marker: PhantomData<T>,
+}
+
+// Box<[T]>と同じく、Tが送受信・共有できるならバッファもそうできる
+unsafe impl<T: Send, A: Allocator + Send> Send for RawToyVec<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for RawToyVec<T, A> {}
+
+impl<T, A: Allocator> RawToyVec<T, A> {
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub(crate) fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            cap: 0,
+            alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    // 確保済みの領域から作る
+    // ptrはallocでcapacity個分のT型の配列として確保した領域か、capacityが0ならダングリングポインタであること
+    pub(crate) unsafe fn from_raw_parts_in(ptr: *mut T, capacity: usize, alloc: A) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            cap: capacity,
+            alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        handle_reserve(Self::try_with_capacity_in(capacity, alloc))
+    }
+
+    pub(crate) fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let mut raw = Self::new_in(alloc);
+        if capacity != 0 && !Self::IS_ZST {
+            raw.ptr = raw.allocate_in_heap(capacity)?;
+            raw.cap = capacity;
+        }
+        Ok(raw)
+    }
+
+    // 要素を初期化せずに領域だけを確保する
+    fn allocate_in_heap(&self, capacity: usize) -> Result<NonNull<T>, TryReserveError> {
+        let layout = array_layout::<T>(capacity)?;
+        // サイズ0の領域はアロケータに要求しない
+        if layout.size() == 0 {
+            return Ok(NonNull::dangling());
+        }
+        match self.alloc.allocate(layout) {
+            Ok(ptr) => Ok(ptr.cast()),
+            Err(_) => Err(TryReserveErrorKind::AllocError { layout }.into()),
+        }
+    }
+
+    // 先頭len個の要素に加えて、少なくともadditional個を格納できるよう容量を広げる
+    // 新しい容量はGの方針で決める
+    pub(crate) fn try_reserve<G: GrowthPolicy>(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let required = required_capacity(len, additional)?;
+        if required <= self.capacity() {
+            return Ok(());
+        }
+        let new_capacity = G::grow(self.cap, required, mem::size_of::<T>()).max(required);
+        self.try_grow_to(new_capacity)
+    }
+
+    // try_reserveと違い、ちょうど必要な分だけ容量を広げる
+    pub(crate) fn try_reserve_exact(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let required = required_capacity(len, additional)?;
+        if required <= self.capacity() {
+            return Ok(());
+        }
+        self.try_grow_to(required)
+    }
+
+    // 容量をnew_capacityに広げる。既存の領域はアロケータのgrowで拡張するので、
+    // アロケータがその場で広げられるなら要素のコピーは発生しない
+    pub(crate) fn try_grow_to(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        debug_assert!(new_capacity >= self.cap);
+        if self.cap == 0 {
+            self.ptr = self.allocate_in_heap(new_capacity)?;
+            self.cap = new_capacity;
+            Ok(())
+        } else {
+            self.reallocate(new_capacity)
+        }
+    }
+
+    // 容量をnew_capacityに縮める。new_capacityが0なら領域を解放する
+    pub(crate) fn shrink_to(&mut self, new_capacity: usize) {
+        debug_assert!(new_capacity <= self.capacity());
+        if Self::IS_ZST {
+            return;
+        }
+        if new_capacity == 0 {
+            self.deallocate();
+            self.ptr = NonNull::dangling();
+            self.cap = 0;
+        } else {
+            handle_reserve(self.reallocate(new_capacity));
+        }
+    }
+
+    // 確保済みの領域の大きさをnew_capacity個分に変える。Tのサイズは0でないこと
+    // 失敗したときは元の領域と容量をそのまま残す
+    fn reallocate(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        let old_layout = array_layout::<T>(self.cap)?;
+        let new_layout = array_layout::<T>(new_capacity)?;
+        let ptr = self.ptr.cast();
+        let result = unsafe {
+            if new_capacity >= self.cap {
+                self.alloc.grow(ptr, old_layout, new_layout)
+            } else {
+                self.alloc.shrink(ptr, old_layout, new_layout)
+            }
+        };
+        match result {
+            Ok(new_ptr) => self.ptr = new_ptr.cast(),
+            Err(_) => return Err(TryReserveErrorKind::AllocError { layout: new_layout }.into()),
+        }
+        self.cap = new_capacity;
+        Ok(())
+    }
+
+    // 確保済みの領域を解放する。ポインタと容量はそのまま残るので、呼び出し側で戻すこと
+    fn deallocate(&mut self) {
+        let layout = Layout::array::<T>(self.cap).unwrap();
+        if layout.size() != 0 {
+            unsafe { self.alloc.deallocate(self.ptr.cast(), layout) }
+        }
+    }
+
+    pub(crate) fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    // サイズ0の型はいくつ並べても領域が要らないので、容量は常にusize::MAX
+    pub(crate) fn capacity(&self) -> usize {
+        if Self::IS_ZST {
+            usize::MAX
+        } else {
+            self.cap
+        }
+    }
+
+    pub(crate) fn allocator(&self) -> &A {
+        &self.alloc
+    }
+}
+
+impl<T, A: Allocator> Drop for RawToyVec<T, A> {
+    // 領域を解放するだけで、中の要素はドロップしない
+    fn drop(&mut self) {
+        self.deallocate();
+    }
+}
+
+fn required_capacity(len: usize, additional: usize) -> Result<usize, TryReserveError> {
+    len.checked_add(additional)
+        .ok_or_else(|| TryReserveErrorKind::CapacityOverflow.into())
+}
+
+// capacity個のT型の値を並べた領域のレイアウト。isize::MAXバイトを超えるならエラー
+fn array_layout<T>(capacity: usize) -> Result<Layout, TryReserveError> {
+    Layout::array::<T>(capacity).map_err(|_| TryReserveErrorKind::CapacityOverflow.into())
+}
+
+// 失敗が許されない操作では、容量の溢れはパニックに、確保の失敗はhandle_alloc_errorにする
+pub(crate) fn handle_reserve<R>(result: Result<R, TryReserveError>) -> R {
+    match result.map_err(|e| e.kind()) {
+        Ok(r) => r,
+        Err(TryReserveErrorKind::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveErrorKind::AllocError { layout }) => handle_alloc_error(layout),
+    }
+}
@@ -0,0 +1,220 @@
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+use crate::{ArrayToyVec, CapacityError, Iter, IterMut, ToyVec};
+
+// 要素がN個以下のうちは自身の中の配列に格納し、ヒープの領域を確保しない
+// N個を超えたらToyVecに移し替え（スピル）、以降はToyVecと同じようにヒープの領域を使う
+//
+// 要素の少ないToyVecを大量に作る場合に、確保と解放の回数を減らせる
+pub struct SmallToyVec<T, const N: usize> {
+    data: Data<T, N>,
+}
+
+enum Data<T, const N: usize> {
+    Inline(ArrayToyVec<T, N>),
+    Heap(ToyVec<T>),
+}
+
+impl<T, const N: usize> SmallToyVec<T, N> {
+    pub fn new() -> Self {
+        Self {
+            data: Data::Inline(ArrayToyVec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.data {
+            Data::Inline(a) => a.len(),
+            Data::Heap(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 配列に格納している間はN、スピルした後はToyVecの容量
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            Data::Inline(_) => N,
+            Data::Heap(v) => v.capacity(),
+        }
+    }
+
+    // 要素をヒープの領域に移し替えたかどうか
+    pub fn spilled(&self) -> bool {
+        matches!(self.data, Data::Heap(_))
+    }
+
+    pub fn push(&mut self, element: T) {
+        match &mut self.data {
+            Data::Inline(a) => {
+                if let Err(CapacityError(element)) = a.push(element) {
+                    // 配列が満杯なので、倍の容量のToyVecに移してから追加する
+                    self.spill(N.saturating_mul(2).max(1)).push(element);
+                }
+            }
+            Data::Heap(v) => v.push(element),
+        }
+    }
+
+    // スピルした後に要素が減っても、shrink_to_fitを呼ぶまでは配列に戻さない
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.data {
+            Data::Inline(a) => a.pop(),
+            Data::Heap(v) => v.pop(),
+        }
+    }
+
+    // 要素数がN以下なら配列に戻してヒープの領域を解放する
+    // そうでなければToyVecのshrink_to_fitと同じく、容量を要素数まで縮める
+    pub fn shrink_to_fit(&mut self) {
+        let v = match &mut self.data {
+            Data::Inline(_) => return,
+            Data::Heap(v) if v.len() > N => return v.shrink_to_fit(),
+            Data::Heap(v) => v,
+        };
+        let len = v.len();
+        let mut a = ArrayToyVec::new();
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), a.as_mut_ptr(), len);
+            a.set_len(len);
+            // 要素は配列にムーブしたので、ToyVecには領域の解放だけをさせる
+            v.set_len(0);
+        }
+        self.data = Data::Inline(a);
+    }
+
+    // 配列の要素をcapacityの容量のToyVecに移し、そのToyVecへの参照を返す
+    fn spill(&mut self, capacity: usize) -> &mut ToyVec<T> {
+        let mut v = ToyVec::with_capacity(capacity);
+        if let Data::Inline(a) = &mut self.data {
+            unsafe {
+                ptr::copy_nonoverlapping(a.as_ptr(), v.as_mut_ptr(), a.len());
+                v.set_len(a.len());
+                // 要素の所有権はToyVecに移ったので、配列側では二重にドロップしないようにする
+                a.set_len(0);
+            }
+        }
+        self.data = Data::Heap(v);
+        match &mut self.data {
+            Data::Heap(v) => v,
+            Data::Inline(_) => unreachable!(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match &self.data {
+            Data::Inline(a) => a.as_slice(),
+            Data::Heap(v) => v.as_slice(),
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.data {
+            Data::Inline(a) => a.as_mut_slice(),
+            Data::Heap(v) => v.as_mut_slice(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    pub fn get_or<'a>(&'a self, index: usize, default: &'a T) -> &'a T {
+        self.get(index).unwrap_or(default)
+    }
+
+    // ToyVecと同じイテレータを返す
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            elements: self.as_slice(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            elements: self.as_mut_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallToyVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for SmallToyVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallToyVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+// 要素数がN以下なら、クローンも配列に格納する
+impl<T: Clone, const N: usize> Clone for SmallToyVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallToyVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut v = Self::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallToyVec<T, N> {
+    // 配列に収まらないとわかっていれば、先にまとめてスピルしておく
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let required = self.len().saturating_add(iter.size_hint().0);
+        if !self.spilled() && required > N {
+            self.spill(required);
+        }
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallToyVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'vec, T, const N: usize> IntoIterator for &'vec SmallToyVec<T, N> {
+    type Item = &'vec T;
+    type IntoIter = Iter<'vec, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'vec, T, const N: usize> IntoIterator for &'vec mut SmallToyVec<T, N> {
+    type Item = &'vec mut T;
+    type IntoIter = IterMut<'vec, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,85 @@
+use core::iter::FusedIterator;
+
+use crate::allocator::{Allocator, Global};
+use crate::drain::Drain;
+use crate::growth::{Doubling, GrowthPolicy};
+use crate::ToyVec;
+
+// ToyVec::spliceが返すイテレータ
+// 取り除いた要素を返し、ドロップされるとその跡をreplace_withの要素で置き換える
+pub struct Splice<'a, I: Iterator + 'a, A: Allocator + 'a = Global, G: GrowthPolicy = Doubling> {
+    drain: Drain<'a, I::Item, A, G>,
+    replace_with: I,
+}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> Splice<'a, I, A, G> {
+    pub(crate) fn new(drain: Drain<'a, I::Item, A, G>, replace_with: I) -> Self {
+        Self {
+            drain,
+            replace_with,
+        }
+    }
+}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> Iterator for Splice<'a, I, A, G> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for Splice<'a, I, A, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> ExactSizeIterator for Splice<'a, I, A, G> {}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> FusedIterator for Splice<'a, I, A, G> {}
+
+impl<'a, I: Iterator, A: Allocator, G: GrowthPolicy> Drop for Splice<'a, I, A, G> {
+    // 範囲の後ろの要素を前に詰めるのは、最後にdrainがドロップされるときに行われる
+    fn drop(&mut self) {
+        // まだ返していない要素を先にドロップする
+        self.drain.by_ref().for_each(drop);
+
+        // 後ろに要素がなければ、末尾に追加するだけでよい
+        if self.drain.tail_is_empty() {
+            self.drain
+                .vec_mut()
+                .extend_from_iter(self.replace_with.by_ref());
+            return;
+        }
+
+        // まず取り除いた範囲の跡を埋める
+        if !self.drain.fill(&mut self.replace_with) {
+            return;
+        }
+
+        // まだ要素があるなら、size_hintの下限の分だけ後ろの要素をずらして埋める
+        let (lower, _) = self.replace_with.size_hint();
+        if lower > 0 {
+            self.drain.move_tail(lower);
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+        }
+
+        // 残りの個数はわからないので、いったん集めてから必要な分だけずらす
+        let mut collected = ToyVec::new();
+        collected.extend_from_iter(self.replace_with.by_ref());
+        if !collected.is_empty() {
+            self.drain.move_tail(collected.len());
+            let mut collected = collected.into_iter();
+            let filled = self.drain.fill(&mut collected);
+            debug_assert!(filled);
+            debug_assert_eq!(collected.len(), 0);
+        }
+    }
+}